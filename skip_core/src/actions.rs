@@ -0,0 +1,158 @@
+//! The actions which can be bound to keys in the config, see [crate::config::KeyBinding].
+//!
+//! Every action has a name and optional parameters, which are checked when the config is validated. Adding an action
+//! only requires adding it to [ACTIONS].
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::app::SkipApp;
+use crate::config::SkipConfig;
+
+pub struct Action {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Check the parameters of a binding, along with any references to the rest of the config.
+    validate: fn(&SkipConfig, &Value) -> eyre::Result<()>,
+    run: fn(&mut SkipApp, &SkipConfig, &Value) -> eyre::Result<()>,
+}
+
+impl Action {
+    /// Find the action called `name`.
+    pub fn get(name: &str) -> Option<&'static Action> {
+        ACTIONS.iter().find(|action| action.name == name)
+    }
+
+    pub fn validate(&self, config: &SkipConfig, params: &Value) -> eyre::Result<()> {
+        (self.validate)(config, params)
+    }
+
+    pub fn run(&self, app: &mut SkipApp, config: &SkipConfig, params: &Value) -> eyre::Result<()> {
+        (self.run)(app, config, params)
+    }
+}
+
+/// All actions which can be bound to keys.
+pub static ACTIONS: &[Action] = &[
+    Action {
+        name: "save_waypoint",
+        description: "Save the current position to the waypoint, or to the numbered waypoint `slot`.",
+        validate: |_, params| parse::<SlotParams>(params).map(drop),
+        run: |app, config, params| {
+            let params = parse::<SlotParams>(params)?;
+            if app.is_pointer_fresh(config, "save a waypoint") {
+                app.save_waypoint(params.slot)?;
+            }
+            Ok(())
+        },
+    },
+    Action {
+        name: "teleport_to_waypoint",
+        description: "Teleport to the waypoint, or to the numbered waypoint `slot`.",
+        validate: |_, params| parse::<SlotParams>(params).map(drop),
+        run: |app, config, params| {
+            let params = parse::<SlotParams>(params)?;
+            if app.is_pointer_fresh(config, "teleport") {
+                app.teleport_to_waypoint(params.slot)?;
+            }
+            Ok(())
+        },
+    },
+    Action {
+        name: "undo_teleport",
+        description: "Teleport back to where the player was before the last teleport.",
+        validate: |_, params| parse::<NoParams>(params).map(drop),
+        run: |app, config, _| {
+            if app.is_pointer_fresh(config, "undo the teleport") {
+                app.undo_teleport()?;
+            }
+            Ok(())
+        },
+    },
+    Action {
+        name: "nudge",
+        description: "Move the player by `x`, `y` and `z`, e.g. to get unstuck.",
+        validate: |_, params| parse::<NudgeParams>(params).map(drop),
+        run: |app, config, params| {
+            let params = parse::<NudgeParams>(params)?;
+            if app.is_pointer_fresh(config, "nudge the player") {
+                app.nudge(params.x, params.y, params.z)?;
+            }
+            Ok(())
+        },
+    },
+    Action {
+        name: "switch_profile",
+        description: "Switch to `profile`, or to the next profile if it isn't given, see `switch_profile_keys`.",
+        validate: |config, params| {
+            let params = parse::<ProfileParams>(params)?;
+            match params.profile {
+                Some(profile) if !config.profiles.contains_key(&profile) => {
+                    eyre::bail!("Unknown profile `{profile}`")
+                }
+                _ => Ok(()),
+            }
+        },
+        run: |app, config, params| {
+            let params = parse::<ProfileParams>(params)?;
+            let profile = match params.profile {
+                Some(profile) => Some(profile),
+                None => app.next_profile(config),
+            };
+            app.switch_profile(profile);
+            Ok(())
+        },
+    },
+    Action {
+        name: "reload",
+        description: "Reload the config and plugin configs, the same as `reload_config_keys`.",
+        validate: |_, params| parse::<NoParams>(params).map(drop),
+        run: |app, _, _| {
+            app.request_reload();
+            Ok(())
+        },
+    },
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoParams {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SlotParams {
+    #[serde(default)]
+    slot: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NudgeParams {
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    z: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileParams {
+    #[serde(default)]
+    profile: Option<String>,
+}
+
+/// Deserialize the parameters of an action, missing parameters are treated as no parameters.
+fn parse<P: DeserializeOwned>(params: &Value) -> eyre::Result<P> {
+    let params = match params {
+        Value::Null => Value::Object(Default::default()),
+        params => params.clone(),
+    };
+
+    serde_path_to_error::deserialize(params).map_err(|e| match e.path().to_string().as_str() {
+        "." => eyre::eyre!("Invalid params: {}", e.inner()),
+        key => eyre::eyre!("Invalid value for `params.{key}`: {}", e.inner()),
+    })
+}
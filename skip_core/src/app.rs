@@ -0,0 +1,687 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::actions::Action;
+use crate::chains::{PointerChainSave, StoredChain, MAX_CONSECUTIVE_MISSES};
+use crate::config::{PointerScanConfig, SkipConfig, DEFAULT_PROFILE_NAME};
+use crate::keys::KeyInput;
+use crate::migrations;
+use crate::platform::Platform;
+use crate::plugins::generic::PointerTypeConfig;
+use crate::plugins::SkipPlugin;
+use crate::scanner::pointers::PointerScanner;
+use crate::scanner::ScanOptions;
+use crate::schema;
+use crate::waypoints::{Waypoint, WaypointSave};
+
+pub static WAYPOINTS_FILE_NAME: &str = "skip_waypoints.json";
+pub static POINTER_CHAINS_FILE_NAME: &str = "skip_pointer_chains.json";
+/// The directory in the data directory containing a directory with the waypoints of every named profile.
+pub static PROFILE_DIRECTORY_NAME: &str = "profiles";
+
+pub struct SkipApp {
+    current_plugin: Box<dyn SkipPlugin>,
+    save_directory: PathBuf,
+    /// The active named profile, [None] for the default profile.
+    profile: Option<String>,
+    waypoints: WaypointSave,
+    /// Where the player was before the last teleport, for undoing it.
+    before_teleport: Option<Waypoint>,
+    /// Whether a reload was requested by a key binding, see [SkipApp::take_reload_request].
+    reload_requested: bool,
+    chains: PointerChainSave,
+    /// Whether the stored chains have been checked against the captured pointer this session.
+    chains_checked: bool,
+    platform: Arc<dyn Platform>,
+}
+
+impl SkipApp {
+    /// Create the app for an already started `plugin`, see [crate::plugins::start_plugin].
+    pub fn new(
+        save_path: impl Into<PathBuf>,
+        plugin: Box<dyn SkipPlugin>,
+        platform: Arc<dyn Platform>,
+    ) -> eyre::Result<Self> {
+        let save_path = save_path.into();
+        let waypoints = get_waypoints(&save_path)?;
+        let chains = get_pointer_chains(&save_path)?;
+
+        let mut app = Self {
+            current_plugin: plugin,
+            save_directory: save_path,
+            profile: None,
+            waypoints,
+            before_teleport: None,
+            reload_requested: false,
+            chains,
+            chains_checked: false,
+            platform,
+        };
+        app.update_fallback_chains();
+
+        Ok(app)
+    }
+
+    pub fn run(&mut self, config: &SkipConfig, keyboard: &mut dyn KeyInput) -> eyre::Result<()> {
+        if let Some(profile) = &self.profile {
+            if !config.profiles.contains_key(profile) {
+                log::warn!("The profile `{profile}` no longer exists, switching to the default profile");
+                self.switch_profile(None);
+            }
+        }
+        if let Some(keys) = &config.switch_profile_keys {
+            if keyboard.all_pressed(keys) {
+                self.switch_profile(self.next_profile(config));
+            }
+        }
+
+        // Bindings are checked against the actions when the config is validated, unknown ones can only be skipped here.
+        for binding in config.profile_keybinds(self.profile.as_deref()) {
+            if keyboard.all_pressed(&binding.keys) {
+                if let Some(action) = Action::get(&binding.action) {
+                    action.run(self, config, &binding.params)?;
+                }
+            }
+        }
+
+        if !self.chains_checked {
+            self.validate_pointer_chains()?;
+        }
+        if let Some(scan_conf) = &config.pointer_scan {
+            if keyboard.all_pressed(&scan_conf.scan) && self.is_pointer_fresh(config, "scan for pointer chains") {
+                self.scan_pointer_chains(scan_conf)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the captured player pointer (if any) has been refreshed recently enough to be used for `action`.
+    pub(crate) fn is_pointer_fresh(&self, config: &SkipConfig, action: &str) -> bool {
+        let (Some(timeout), Some(captured)) = (config.stale_pointer_timeout, self.current_plugin.captured_pointer())
+        else {
+            return true;
+        };
+
+        if captured.is_stale(Duration::from_secs_f32(timeout.max(0.0))) {
+            log::warn!(
+                "The player pointer ({:#X}) hasn't been seen for {:.1?}, it's likely stale (e.g. after a loading screen). \
+                Refusing to {action}, move around a bit and try again",
+                captured.address,
+                captured.age()
+            );
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Save the current position to the waypoint, or to the numbered waypoint `slot`.
+    pub fn save_waypoint(&mut self, slot: Option<u32>) -> eyre::Result<()> {
+        let coords = match self.current_plugin.get_current_coordinates() {
+            Ok(coords) => coords,
+            Err(e) => {
+                log::warn!("Failed to read the player coordinates, not saving a waypoint: {e}");
+                return Ok(());
+            }
+        };
+
+        if let Some(coords) = coords {
+            match slot {
+                Some(slot) => {
+                    self.waypoints.slots.insert(slot, coords);
+                }
+                None => self.waypoints.most_recent = Some(coords),
+            }
+            let directory = self.waypoint_directory();
+            std::fs::create_dir_all(&directory)?;
+            // Profiles are stored in `profiles/<name>`, the schemas are next to the default profile's waypoints.
+            let depth = if self.profile.is_some() { 2 } else { 0 };
+            let out = schema::to_nested_json_with_schema(&self.waypoints, depth)?;
+            std::fs::write(directory.join(WAYPOINTS_FILE_NAME), out)?;
+            log::info!("Saved new waypoint{} at: {coords:#?}", slot_name(slot));
+        } else {
+            log::info!("No player pointer was found, couldn't save coordinates!")
+        }
+
+        Ok(())
+    }
+
+    /// Reload the config of the current plugin, on failure the previous config stays in use.
+    pub fn reload_plugin_config(&mut self) {
+        let name = self.current_plugin.identifiers().plugin_name;
+        match self.current_plugin.reload_config() {
+            Ok(()) => {
+                log::debug!("Reloaded the config of plugin `{name}`");
+                self.update_fallback_chains();
+            }
+            Err(e) => log::error!("Failed to reload the config of plugin `{name}`, keeping the previous one: {e:#}"),
+        }
+    }
+
+    /// Reload the waypoints after they were changed on disk, e.g. by hand or by another instance.
+    pub fn reload_waypoints(&mut self) {
+        match get_waypoints(&self.waypoint_directory()) {
+            Ok(waypoints) => {
+                if waypoints.most_recent != self.waypoints.most_recent || waypoints.slots != self.waypoints.slots {
+                    log::info!(
+                        "Reloaded waypoints: {:?}, slots: {:?}",
+                        waypoints.most_recent,
+                        waypoints.slots
+                    );
+                }
+                self.waypoints = waypoints;
+            }
+            Err(e) => log::error!("Failed to reload the waypoints, keeping the previous ones: {e:#}"),
+        }
+    }
+
+    /// Switch to `profile` ([None] for the default profile), and load its waypoints.
+    ///
+    /// If its waypoints can't be loaded the profile starts without a waypoint, they're only overwritten once a new one is
+    /// saved.
+    pub fn switch_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+        self.waypoints = get_waypoints(&self.waypoint_directory()).unwrap_or_else(|e| {
+            log::error!("Failed to load the waypoints of the profile: {e:#}");
+            Default::default()
+        });
+
+        log::info!(
+            "Switched to profile `{}`, waypoint: {:?}",
+            self.profile.as_deref().unwrap_or(DEFAULT_PROFILE_NAME),
+            self.waypoints.most_recent
+        );
+    }
+
+    /// The profile after the active one, going from the default profile through the named profiles in order and back.
+    pub(crate) fn next_profile(&self, config: &SkipConfig) -> Option<String> {
+        let mut names = config.profiles.keys();
+        match &self.profile {
+            None => names.next(),
+            Some(current) => names.skip_while(|name| *name != current).nth(1),
+        }
+        .cloned()
+    }
+
+    /// Request the runner to reload the config, plugin configs and waypoints.
+    pub(crate) fn request_reload(&mut self) {
+        self.reload_requested = true;
+    }
+
+    /// Whether a reload was requested since the last call.
+    pub fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
+    }
+
+    /// The directory containing the waypoints of the active profile.
+    fn waypoint_directory(&self) -> PathBuf {
+        match &self.profile {
+            Some(name) => self.save_directory.join(PROFILE_DIRECTORY_NAME).join(name),
+            None => self.save_directory.clone(),
+        }
+    }
+
+    /// Teleport to the waypoint, or to the numbered waypoint `slot`.
+    pub fn teleport_to_waypoint(&mut self, slot: Option<u32>) -> eyre::Result<()> {
+        // TODO: Add warning (maybe when the player pointer changes? as that seems to indicate a change in area in most games
+        // as it gets re-allocated) for when a player tries to teleport to a waypoint made in a different area.
+        let waypoint = match slot {
+            Some(slot) => self.waypoints.slots.get(&slot),
+            None => self.waypoints.most_recent.as_ref(),
+        };
+
+        if let Some(coords) = waypoint.copied() {
+            self.teleport(coords);
+        } else {
+            log::info!("No waypoint{} exists as of yet, not teleporting", slot_name(slot))
+        }
+
+        Ok(())
+    }
+
+    /// Teleport back to where the player was before the last teleport, undoing the undo works as well.
+    pub fn undo_teleport(&mut self) -> eyre::Result<()> {
+        match self.before_teleport {
+            Some(coords) => self.teleport(coords),
+            None => log::info!("There was no teleport yet, nothing to undo"),
+        }
+
+        Ok(())
+    }
+
+    /// Move the player relative to their current position.
+    pub fn nudge(&mut self, x: f32, y: f32, z: f32) -> eyre::Result<()> {
+        match self.current_plugin.get_current_coordinates() {
+            Ok(Some(coords)) => self.teleport(Waypoint {
+                x: coords.x + x,
+                y: coords.y + y,
+                z: coords.z + z,
+            }),
+            Ok(None) => log::info!("No player pointer was found, couldn't nudge the player"),
+            Err(e) => log::warn!("Failed to read the player coordinates, not nudging: {e}"),
+        }
+
+        Ok(())
+    }
+
+    fn teleport(&mut self, coords: Waypoint) {
+        let before = self.current_plugin.get_current_coordinates().ok().flatten();
+
+        let now = Instant::now();
+        // Execute the write several million times to try get around potential race conditions, where the game itself
+        // overwrites our teleport location instantly (Wo Long).
+        while now.elapsed() < Duration::from_millis(16) {
+            if let Err(e) = self.current_plugin.set_current_coordinates(coords) {
+                log::info!("Failed to teleport, maybe the player pointer wasn't initialized yet? {e:?}");
+                return;
+            }
+        }
+
+        if before.is_some() {
+            self.before_teleport = before;
+        }
+        log::info!("Teleported player to: {coords:#?}");
+    }
+
+    fn scan_pointer_chains(&mut self, config: &PointerScanConfig) -> eyre::Result<()> {
+        let Some(target) = self.current_plugin.captured_pointer() else {
+            log::info!("No player pointer was captured yet, can't scan for pointer chains");
+            return Ok(());
+        };
+
+        let target = target.address;
+        log::info!("Scanning for pointer chains to {target:#X}, this might take a while...");
+        let source = self.platform.memory_source(ScanOptions {
+            max_region_size: None,
+            include_modules: true,
+        });
+        let found = PointerScanner::new(config.settings.clone()).scan(&*source, target);
+
+        if found.is_empty() {
+            log::info!("No pointer chains were found, try increasing the maximum depth or offset");
+            return Ok(());
+        }
+
+        let stored = self.plugin_chains();
+        for chain in found {
+            log::info!(
+                "Found pointer chain `{chain}`: {}",
+                serde_json::to_string(&PointerTypeConfig::Chain(chain.clone()))?
+            );
+
+            if stored.iter().all(|existing| existing.chain != chain) {
+                stored.push(StoredChain::new(chain));
+            }
+        }
+
+        log::info!("Saved chains will be validated the next time the game is started");
+        self.save_pointer_chains()
+    }
+
+    /// Check the stored chains against the captured pointer, chains which repeatedly didn't lead to it are removed.
+    ///
+    /// This is only done once per session, as a chain which survives a game restart is unlikely to be a coincidence.
+    fn validate_pointer_chains(&mut self) -> eyre::Result<()> {
+        let Some(target) = self.current_plugin.captured_pointer().map(|ptr| ptr.address) else {
+            return Ok(());
+        };
+        self.chains_checked = true;
+
+        let platform = self.platform.clone();
+        let stored = self.plugin_chains();
+        if stored.is_empty() {
+            return Ok(());
+        }
+
+        let before = stored.len();
+        let mut valid = 0;
+        for stored in stored.iter_mut() {
+            let hit = stored
+                .chain
+                .resolve(&*platform)
+                .map(|ptr| ptr == target)
+                .unwrap_or_default();
+            stored.record(hit);
+            valid += hit as usize;
+        }
+        stored.retain(|stored| !stored.is_expired());
+        stored.sort_by_key(|stored| std::cmp::Reverse(stored.validations));
+
+        log::info!(
+            "Validated pointer chains, {valid} of {before} are valid. {} were removed after missing {} times in a row",
+            before - stored.len(),
+            MAX_CONSECUTIVE_MISSES
+        );
+        self.update_fallback_chains();
+        self.save_pointer_chains()
+    }
+
+    fn update_fallback_chains(&mut self) {
+        let validated = self
+            .plugin_chains()
+            .iter()
+            .filter(|stored| stored.is_validated())
+            .map(|stored| stored.chain.clone())
+            .collect();
+
+        self.current_plugin.set_fallback_chains(validated);
+    }
+
+    /// The stored chains belonging to the current plugin.
+    fn plugin_chains(&mut self) -> &mut Vec<StoredChain> {
+        let name = self.current_plugin.identifiers().plugin_name;
+        self.chains.plugins.entry(name).or_default()
+    }
+
+    fn save_pointer_chains(&self) -> eyre::Result<()> {
+        let save_file = self.save_directory.join(POINTER_CHAINS_FILE_NAME);
+        let out = serde_json::to_string_pretty(&self.chains)?;
+        std::fs::write(save_file, out)?;
+        Ok(())
+    }
+}
+
+/// ` 3` for slot 3, for log messages.
+fn slot_name(slot: Option<u32>) -> String {
+    slot.map(|slot| format!(" {slot}")).unwrap_or_default()
+}
+
+fn get_pointer_chains(save_path: &Path) -> eyre::Result<PointerChainSave> {
+    let save_file = save_path.join(POINTER_CHAINS_FILE_NAME);
+
+    if save_file.exists() {
+        let file = migrations::read_migrated::<PointerChainSave>(&save_file)?;
+        Ok(serde_json::from_slice(&file)?)
+    } else {
+        Ok(Default::default())
+    }
+}
+
+fn get_waypoints(save_path: &Path) -> eyre::Result<WaypointSave> {
+    let save_file = save_path.join(WAYPOINTS_FILE_NAME);
+
+    if save_file.exists() {
+        let file = migrations::read_migrated::<WaypointSave>(&save_file)?;
+        Ok(serde_json::from_slice(&file)?)
+    } else {
+        Ok(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeyBinding;
+    use crate::format::Format;
+    use crate::keys::{Chord, VirtualKey};
+    use crate::memory::MockMemory;
+    use crate::migrations::Versioned;
+    use crate::platform::MockPlatform;
+    use crate::plugins::context::MockRegisters;
+    use crate::plugins::generic::{ConfigBasedPlugin, Register};
+
+    const PLAYER: usize = 0x3000;
+
+    fn app(directory: &Path) -> (SkipApp, Arc<MockPlatform>) {
+        app_with(directory, "[position.AbsolutePtr]\nAbsolute = \"0x3000\"")
+    }
+
+    /// `game.exe` contains `0F 28` at `0x1000`, see [hit_intercept].
+    fn app_with(directory: &Path, position: &str) -> (SkipApp, Arc<MockPlatform>) {
+        let mut memory = MockMemory::new();
+        memory.map(PLAYER, 0x100, true);
+        memory.map_bytes(0x1000, vec![0x0F, 0x28], false);
+        memory.add_module("game.exe", 0x1000, 2);
+        let platform = Arc::new(MockPlatform::new(Arc::new(memory), Some("game.exe")));
+
+        let config = format!(
+            r#"version = 1
+            [identifiers]
+            plugin_name = "Test"
+            expected_exe_name = "game.exe"
+            {position}
+            [pointer_offsets]
+            x = 0
+            y = 4
+            z = 8"#
+        );
+        let config = Format::Toml.parse(config.as_bytes()).unwrap();
+        let mut plugin = ConfigBasedPlugin::from_config(config, platform.clone());
+        plugin.start().unwrap();
+
+        let app = SkipApp::new(directory, Box::new(plugin), platform.clone()).unwrap();
+        (app, platform)
+    }
+
+    fn position(platform: &MockPlatform) -> Waypoint {
+        let memory = platform.memory();
+        let read = |offset| memory.read_f32(PLAYER + offset).unwrap();
+        Waypoint {
+            x: read(0),
+            y: read(4),
+            z: read(8),
+        }
+    }
+
+    fn move_to(platform: &MockPlatform, x: f32, y: f32, z: f32) {
+        let memory = platform.memory();
+        for (offset, value) in [(0, x), (4, y), (8, z)] {
+            memory.write_f32(PLAYER + offset, value).unwrap();
+        }
+    }
+
+    const INTERCEPT: &str = r#"[position.InterceptPtr]
+        intercept_signature = "0F 28"
+        register = "Rcx""#;
+
+    /// Let the intercept capture the player pointer.
+    fn hit_intercept(platform: &MockPlatform) {
+        assert_eq!(
+            platform.hit(0x1000, &MockRegisters::new().with(Register::Rcx, PLAYER as u64)),
+            1
+        );
+    }
+
+    #[test]
+    fn stale_pointer_is_refused() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app_with(directory.path(), INTERCEPT);
+        let config = SkipConfig {
+            stale_pointer_timeout: Some(0.001),
+            ..Default::default()
+        };
+        let run = |app: &mut SkipApp, action: &str, params: serde_json::Value| {
+            Action::get(action).unwrap().run(app, &config, &params).unwrap();
+        };
+
+        move_to(&platform, 1.0, 2.0, 3.0);
+        hit_intercept(&platform);
+        run(&mut app, "save_waypoint", serde_json::Value::Null);
+        move_to(&platform, 4.0, 5.0, 6.0);
+        hit_intercept(&platform);
+        run(&mut app, "teleport_to_waypoint", serde_json::Value::Null);
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+        let saved = std::fs::read(directory.path().join(WAYPOINTS_FILE_NAME)).unwrap();
+
+        // Once the pointer has aged past the timeout every action touching the player is refused.
+        std::thread::sleep(Duration::from_millis(20));
+        move_to(&platform, 7.0, 8.0, 9.0);
+        run(&mut app, "save_waypoint", serde_json::json!({ "slot": 1 }));
+        run(&mut app, "save_waypoint", serde_json::Value::Null);
+        run(&mut app, "teleport_to_waypoint", serde_json::Value::Null);
+        run(&mut app, "undo_teleport", serde_json::Value::Null);
+        run(&mut app, "nudge", serde_json::json!({ "x": 1.0 }));
+
+        assert_eq!(position(&platform), Waypoint { x: 7.0, y: 8.0, z: 9.0 });
+        assert_eq!(app.waypoints.most_recent, Some(Waypoint { x: 1.0, y: 2.0, z: 3.0 }));
+        assert!(app.waypoints.slots.is_empty());
+        assert_eq!(
+            std::fs::read(directory.path().join(WAYPOINTS_FILE_NAME)).unwrap(),
+            saved
+        );
+
+        // Seeing the pointer again makes it usable.
+        hit_intercept(&platform);
+        run(&mut app, "nudge", serde_json::json!({ "x": 1.0 }));
+        assert_eq!(position(&platform), Waypoint { x: 8.0, y: 8.0, z: 9.0 });
+    }
+
+    #[test]
+    fn save_and_teleport() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app(directory.path());
+
+        // Without a waypoint nothing happens.
+        move_to(&platform, 1.0, 2.0, 3.0);
+        app.teleport_to_waypoint(None).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+
+        app.save_waypoint(None).unwrap();
+        move_to(&platform, 10.0, 20.0, 30.0);
+        app.teleport_to_waypoint(None).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+
+        app.undo_teleport().unwrap();
+        assert_eq!(
+            position(&platform),
+            Waypoint {
+                x: 10.0,
+                y: 20.0,
+                z: 30.0
+            }
+        );
+    }
+
+    #[test]
+    fn slots() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app(directory.path());
+
+        move_to(&platform, 1.0, 1.0, 1.0);
+        app.save_waypoint(Some(1)).unwrap();
+        move_to(&platform, 2.0, 2.0, 2.0);
+        app.save_waypoint(None).unwrap();
+
+        move_to(&platform, 0.0, 0.0, 5.0);
+        app.teleport_to_waypoint(Some(2)).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 0.0, y: 0.0, z: 5.0 });
+        app.teleport_to_waypoint(Some(1)).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 1.0, z: 1.0 });
+        app.teleport_to_waypoint(None).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 2.0, y: 2.0, z: 2.0 });
+    }
+
+    /// Reports exactly the chord it was given as pressed.
+    struct PressedKeys(Chord);
+
+    impl KeyInput for PressedKeys {
+        fn all_pressed(&mut self, keys: &[VirtualKey]) -> bool {
+            !keys.is_empty() && *self.0 == *keys
+        }
+    }
+
+    fn press(app: &mut SkipApp, config: &SkipConfig, keys: &str) {
+        app.run(config, &mut PressedKeys(keys.parse().unwrap())).unwrap();
+    }
+
+    #[test]
+    fn keybinds_run_actions() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app(directory.path());
+        let slot = serde_json::json!({ "slot": 1 });
+        let config = SkipConfig {
+            keybinds: vec![
+                KeyBinding {
+                    params: slot.clone(),
+                    ..KeyBinding::new("F9".parse().unwrap(), "save_waypoint")
+                },
+                KeyBinding {
+                    params: slot.clone(),
+                    ..KeyBinding::new("F10".parse().unwrap(), "teleport_to_waypoint")
+                },
+                KeyBinding {
+                    params: slot,
+                    ..KeyBinding::new("Ctrl+T".parse().unwrap(), "teleport_to_waypoint")
+                },
+            ],
+            ..SkipConfig::default()
+        };
+
+        move_to(&platform, 1.0, 2.0, 3.0);
+        press(&mut app, &config, "F9");
+        assert_eq!(app.waypoints.slots.get(&1), Some(&Waypoint { x: 1.0, y: 2.0, z: 3.0 }));
+        assert_eq!(app.waypoints.most_recent, None);
+
+        for keys in ["F10", "Ctrl+T"] {
+            move_to(&platform, 0.0, 0.0, 5.0);
+            press(&mut app, &config, "F8");
+            assert_eq!(position(&platform), Waypoint { x: 0.0, y: 0.0, z: 5.0 });
+            press(&mut app, &config, keys);
+            assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 }, "{keys}");
+        }
+    }
+
+    #[test]
+    fn waypoints_are_persisted() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app(directory.path());
+        move_to(&platform, 1.0, 2.0, 3.0);
+        app.save_waypoint(None).unwrap();
+        app.save_waypoint(Some(4)).unwrap();
+
+        let (mut app, platform) = self::app(directory.path());
+        app.teleport_to_waypoint(Some(4)).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+
+        let saved: WaypointSave =
+            serde_json::from_slice(&std::fs::read(directory.path().join(WAYPOINTS_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(saved.version, WaypointSave::VERSION);
+        assert_eq!(saved.most_recent, Some(Waypoint { x: 1.0, y: 2.0, z: 3.0 }));
+    }
+
+    #[test]
+    fn profiles_have_their_own_waypoints() {
+        let directory = tempfile::tempdir().unwrap();
+        let (mut app, platform) = app(directory.path());
+        move_to(&platform, 1.0, 2.0, 3.0);
+        app.save_waypoint(None).unwrap();
+
+        app.switch_profile(Some("practice".into()));
+        move_to(&platform, 4.0, 5.0, 6.0);
+        app.teleport_to_waypoint(None).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 4.0, y: 5.0, z: 6.0 });
+        app.save_waypoint(None).unwrap();
+        assert!(directory
+            .path()
+            .join(PROFILE_DIRECTORY_NAME)
+            .join("practice")
+            .join(WAYPOINTS_FILE_NAME)
+            .exists());
+
+        app.switch_profile(None);
+        app.teleport_to_waypoint(None).unwrap();
+        assert_eq!(position(&platform), Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+    }
+
+    #[test]
+    fn waypoints_reference_schema() {
+        let directory = tempfile::tempdir().unwrap();
+        schema::write_schemas(directory.path()).unwrap();
+        let (mut app, _) = app(directory.path());
+        app.save_waypoint(None).unwrap();
+        app.switch_profile(Some("practice".into()));
+        app.save_waypoint(None).unwrap();
+
+        for waypoints in [
+            directory.path().to_path_buf(),
+            directory.path().join(PROFILE_DIRECTORY_NAME).join("practice"),
+        ] {
+            let saved: serde_json::Value =
+                serde_json::from_slice(&std::fs::read(waypoints.join(WAYPOINTS_FILE_NAME)).unwrap()).unwrap();
+            let reference = saved["$schema"].as_str().unwrap();
+            assert!(waypoints.join(reference).is_file(), "{reference}");
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::migrations;
+use crate::migrations::{Migration, Versioned};
+use crate::plugins::generic::PointerChain;
+
+/// The amount of sessions in a row in which a chain may miss the captured pointer before it's removed.
+///
+/// A single miss isn't enough, as the game might've captured a different (e.g. cutscene) instance of the player first.
+pub const MAX_CONSECUTIVE_MISSES: u32 = 3;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PointerChainSave {
+    /// The format version of this file, files from older versions are upgraded automatically.
+    #[serde(default = "crate::migrations::current_version::<PointerChainSave>")]
+    pub version: u32,
+    /// All found chains, keyed by the name of the plugin they were found with.
+    pub plugins: HashMap<String, Vec<StoredChain>>,
+}
+
+impl Default for PointerChainSave {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            plugins: HashMap::new(),
+        }
+    }
+}
+
+impl Versioned for PointerChainSave {
+    const NAME: &'static str = "pointer chains";
+    const MIGRATIONS: &'static [Migration] = &[migrations::add_version];
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredChain {
+    pub chain: PointerChain,
+    /// The amount of sessions in which this chain led to the same pointer as the one captured by the plugin, minus the
+    /// sessions in which it didn't.
+    ///
+    /// Only chains which have been validated at least once are used.
+    pub validations: u32,
+    /// The amount of sessions in a row in which this chain didn't lead to the captured pointer.
+    #[serde(default)]
+    pub misses: u32,
+}
+
+impl StoredChain {
+    pub fn new(chain: PointerChain) -> Self {
+        Self {
+            chain,
+            validations: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn is_validated(&self) -> bool {
+        self.validations > 0
+    }
+
+    /// Record whether the chain led to the captured pointer this session.
+    pub fn record(&mut self, valid: bool) {
+        if valid {
+            self.validations += 1;
+            self.misses = 0;
+        } else {
+            self.validations = self.validations.saturating_sub(1);
+            self.misses += 1;
+        }
+    }
+
+    /// Whether the chain missed too often in a row to still be worth keeping, see [MAX_CONSECUTIVE_MISSES].
+    pub fn is_expired(&self) -> bool {
+        self.misses >= MAX_CONSECUTIVE_MISSES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::generic::RelativePointer;
+
+    fn chain() -> StoredChain {
+        StoredChain::new(PointerChain {
+            base: RelativePointer::new("game.exe", 0x10),
+            offsets: vec![0x80],
+        })
+    }
+
+    #[test]
+    fn misses_age_validations() {
+        let mut stored = chain();
+        stored.record(true);
+        stored.record(true);
+        assert_eq!(stored.validations, 2);
+
+        stored.record(false);
+        assert!(stored.is_validated());
+        assert!(!stored.is_expired());
+
+        stored.record(false);
+        assert!(!stored.is_validated());
+        assert!(!stored.is_expired());
+
+        stored.record(false);
+        assert_eq!(stored.validations, 0);
+        assert!(stored.is_expired());
+    }
+
+    #[test]
+    fn hit_resets_misses() {
+        let mut stored = chain();
+        for _ in 0..MAX_CONSECUTIVE_MISSES - 1 {
+            stored.record(false);
+        }
+        stored.record(true);
+        assert_eq!(stored.misses, 0);
+        assert!(stored.is_validated());
+
+        for _ in 0..MAX_CONSECUTIVE_MISSES - 1 {
+            stored.record(false);
+        }
+        assert!(!stored.is_expired());
+    }
+
+    #[test]
+    fn unversioned_save() {
+        let mut document = serde_json::json!({
+            "plugins": { "Test": [{ "chain": { "base": "game.exe+10", "offsets": ["0x80"] }, "validations": 2 }] }
+        });
+        assert_eq!(migrations::migrate::<PointerChainSave>(&mut document).unwrap(), Some(0));
+
+        let save: PointerChainSave = serde_json::from_value(document).unwrap();
+        assert_eq!(save.version, PointerChainSave::VERSION);
+        assert_eq!(save.plugins["Test"][0].validations, 2);
+        assert_eq!(save.plugins["Test"][0].misses, 0);
+    }
+}
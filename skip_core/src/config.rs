@@ -0,0 +1,850 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use eyre::Context;
+
+use crate::actions::{Action, ACTIONS};
+use crate::format::Format;
+use crate::keys::{Chord, VirtualKey};
+use crate::migrations;
+use crate::migrations::{Migration, Versioned};
+use crate::scanner::coordinates::ScanSettings;
+use crate::scanner::pointers::PointerScanSettings;
+use crate::schema;
+
+/// The name of the config file without extension, it can be written in any [Format].
+pub const CONFIG_FILE_STEM: &str = "skip_rs_config";
+/// The name under which the profile using the top level keybinds is logged, it can't be used for named profiles.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone)]
+pub struct SkipConfig {
+    /// The format version of this file, files from older versions are upgraded automatically.
+    #[serde(default = "crate::migrations::current_version::<SkipConfig>")]
+    pub version: u32,
+    /// Whether to open a console for logging
+    pub console: bool,
+    /// If set, will allow the config to be reloaded during gameplay by providing the given key codes.
+    pub reload_config_keys: Option<Chord>,
+    /// Whether to reload the config, plugin configs and waypoints as soon as they're changed on disk.
+    #[serde(default = "default_hot_reload")]
+    pub hot_reload: bool,
+    /// The amount of seconds after which a captured player pointer which hasn't been seen by the intercept is considered
+    /// stale. Saving and teleporting is refused while the pointer is stale.
+    ///
+    /// Set to `null` (or `false` in TOML) for games where the intercepted code doesn't run every frame.
+    #[serde(default = "default_stale_pointer_timeout", deserialize_with = "nullable")]
+    pub stale_pointer_timeout: Option<f32>,
+    /// The actions bound to keys. An action can be bound to several chords, each with their own parameters.
+    pub keybinds: Vec<KeyBinding>,
+    /// Whether keybinds also work while the game isn't focused. Only used by hosts which can't tell which window is
+    /// focused (e.g. for native Wayland games on Linux), keybinds are ignored there unless this is set.
+    #[serde(default)]
+    pub global_keybinds: bool,
+    /// The directory in which waypoints, pointer chains and discovery results are stored. Environment variables such as
+    /// `%APPDATA%` or `$HOME` are expanded, and relative paths are relative to the directory containing this config.
+    ///
+    /// Defaults to the directory containing this config.
+    #[serde(default)]
+    pub data_directory: Option<String>,
+    /// Named profiles, e.g. one per route or character, each with their own waypoints and optionally their own keybinds.
+    /// The waypoints of a profile are stored in `profiles/<name>` in the data directory.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    /// If set, pressing these keys switches to the next profile, going from the default profile through `profiles` in
+    /// alphabetical order and back.
+    #[serde(default)]
+    pub switch_profile_keys: Option<Chord>,
+    /// If set, enables the coordinate discovery tool for finding the player position in games without a plugin.
+    #[serde(default)]
+    pub discovery: Option<DiscoveryConfig>,
+    /// If set, enables scanning for static pointer chains to the pointer captured by the current plugin.
+    #[serde(default)]
+    pub pointer_scan: Option<PointerScanConfig>,
+    /// Overrides for which plugin is used for the current game.
+    #[serde(default)]
+    pub plugins: PluginSelectionConfig,
+    /// If set, starting a plugin is retried in the background while no plugin could be started yet, e.g. because the
+    /// game loads the module containing the hooked code some time after launching.
+    #[serde(default = "default_deferred_start", deserialize_with = "nullable")]
+    pub deferred_start: Option<DeferredStartConfig>,
+}
+
+impl Default for SkipConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            console: false,
+            reload_config_keys: Some(Chord(vec![
+                VirtualKey::VK_CONTROL,
+                VirtualKey::VK_SHIFT,
+                VirtualKey::VK_R,
+            ])),
+            hot_reload: default_hot_reload(),
+            stale_pointer_timeout: default_stale_pointer_timeout(),
+            keybinds: vec![
+                KeyBinding::new(Chord(vec![VirtualKey::VK_F9]), "save_waypoint"),
+                KeyBinding::new(Chord(vec![VirtualKey::VK_F10]), "teleport_to_waypoint"),
+            ],
+            global_keybinds: false,
+            data_directory: None,
+            profiles: Default::default(),
+            switch_profile_keys: None,
+            discovery: None,
+            pointer_scan: None,
+            plugins: Default::default(),
+            deferred_start: default_deferred_start(),
+        }
+    }
+}
+
+impl SkipConfig {
+    /// The directory in which data is stored, for a config in `config_directory`, see [SkipConfig::data_directory].
+    ///
+    /// # Errors
+    ///
+    /// If an environment variable used in the path isn't set.
+    pub fn data_directory(&self, config_directory: &Path) -> eyre::Result<PathBuf> {
+        match &self.data_directory {
+            // `join` keeps absolute paths as they are.
+            Some(directory) => Ok(config_directory.join(crate::paths::expand_env(directory)?)),
+            None => Ok(config_directory.to_path_buf()),
+        }
+    }
+
+    /// The keybinds of `profile`, being the top level keybinds for the default profile or profiles without their own.
+    pub fn profile_keybinds(&self, profile: Option<&str>) -> &[KeyBinding] {
+        profile
+            .and_then(|name| self.profiles.get(name))
+            .and_then(|profile| profile.keybinds.as_ref())
+            .unwrap_or(&self.keybinds)
+    }
+}
+
+impl Versioned for SkipConfig {
+    const NAME: &'static str = "config";
+    const MIGRATIONS: &'static [Migration] = &[migrations::add_version, keybinds_to_list];
+}
+
+/// Version 1 -> 2, `keybinds` changed from an object with the keys of every action to a list of bindings.
+fn keybinds_to_list(document: &mut serde_json::Value) -> eyre::Result<()> {
+    fn convert(keybinds: Option<&mut serde_json::Value>) {
+        if let Some(keybinds) = keybinds.filter(|keybinds| keybinds.is_object()) {
+            let actions = std::mem::take(keybinds);
+            *keybinds = actions
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(action, keys)| serde_json::json!({ "keys": keys, "action": action }))
+                .collect();
+        }
+    }
+
+    convert(document.get_mut("keybinds"));
+    if let Some(profiles) = document
+        .get_mut("profiles")
+        .and_then(|profiles| profiles.as_object_mut())
+    {
+        for profile in profiles.values_mut() {
+            convert(profile.get_mut("keybinds"));
+        }
+    }
+
+    Ok(())
+}
+
+fn default_hot_reload() -> bool {
+    true
+}
+
+fn default_stale_pointer_timeout() -> Option<f32> {
+    Some(5.0)
+}
+
+fn default_deferred_start() -> Option<DeferredStartConfig> {
+    Some(Default::default())
+}
+
+/// Deserialize an optional setting which is enabled by default, as TOML has no `null` `false` disables it as well.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+    use serde::de::{Error, IntoDeserializer, MapAccess, SeqAccess, Unexpected, Visitor};
+    use serde::Deserialize;
+
+    struct NullableVisitor<T>(std::marker::PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for NullableVisitor<T> {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a value, or null or false to disable it")
+        }
+
+        fn visit_bool<E: Error>(self, v: bool) -> Result<Self::Value, E> {
+            if v {
+                Err(E::invalid_value(Unexpected::Bool(v), &self))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+            T::deserialize(v.into_deserializer()).map(Some)
+        }
+
+        fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+            T::deserialize(v.into_deserializer()).map(Some)
+        }
+
+        fn visit_f64<E: Error>(self, v: f64) -> Result<Self::Value, E> {
+            T::deserialize(v.into_deserializer()).map(Some)
+        }
+
+        fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            T::deserialize(SeqAccessDeserializer::new(seq)).map(Some)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            T::deserialize(MapAccessDeserializer::new(map)).map(Some)
+        }
+    }
+
+    deserializer.deserialize_any(NullableVisitor(std::marker::PhantomData))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq)]
+pub struct KeyBinding {
+    /// The keys which have to be held together.
+    pub keys: Chord,
+    /// The name of the action, the available actions and their parameters are listed in the README.
+    #[schemars(schema_with = "action_schema")]
+    pub action: String,
+    /// The parameters of the action, if it has any, e.g. `{ slot = 1 }` to save to a numbered waypoint.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub params: serde_json::Value,
+}
+
+impl KeyBinding {
+    /// Bind `action` without parameters to `keys`.
+    pub fn new(keys: Chord, action: &str) -> Self {
+        Self {
+            keys,
+            action: action.to_string(),
+            params: serde_json::Value::Null,
+        }
+    }
+}
+
+/// The names of all actions, along with their descriptions for editors.
+fn action_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    use schemars::schema::{InstanceType, Metadata, SchemaObject, SubschemaValidation};
+
+    let actions = crate::actions::ACTIONS.iter().map(|action| {
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(action.description.to_string()),
+                ..Default::default()
+            })),
+            const_value: Some(action.name.into()),
+            ..Default::default()
+        }
+        .into()
+    });
+
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        subschemas: Some(Box::new(SubschemaValidation {
+            one_of: Some(actions.collect()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, Default)]
+pub struct ProfileConfig {
+    /// If set, replaces the top level keybinds while this profile is active.
+    #[serde(default)]
+    pub keybinds: Option<Vec<KeyBinding>>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, Default)]
+pub struct PluginSelectionConfig {
+    /// Always use the plugin with this name, even if its module/exe doesn't match the game.
+    #[serde(default)]
+    pub force: Option<String>,
+    /// Names of plugins which should never be used, e.g. a built-in plugin conflicting with a user plugin.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone)]
+pub struct DeferredStartConfig {
+    /// Give up on starting a plugin after this many seconds.
+    pub timeout: f32,
+    /// The maximum amount of seconds between attempts, the first retry happens after a quarter of a second and the
+    /// delay doubles after every attempt.
+    pub max_interval: f32,
+}
+
+impl Default for DeferredStartConfig {
+    fn default() -> Self {
+        Self {
+            timeout: 120.0,
+            max_interval: 5.0,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone)]
+pub struct DiscoveryConfig {
+    /// Start a new scan by taking a snapshot of all writable memory.
+    pub new_scan: Chord,
+    /// Narrow the scan down to values which changed, press after the player has moved since the last key press.
+    pub moved: Chord,
+    /// Narrow the scan down to values which stayed the same, press after the player has stood still since the last key press.
+    pub stood_still: Chord,
+    /// Once at most this many candidates remain they'll be logged and saved to the discovery results file.
+    pub max_reported: usize,
+    /// Regions larger than this many bytes are skipped while taking a snapshot.
+    pub max_region_size: Option<usize>,
+    #[serde(default)]
+    pub settings: ScanSettings,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            new_scan: Chord(vec![VirtualKey::VK_CONTROL, VirtualKey::VK_F5]),
+            moved: Chord(vec![VirtualKey::VK_CONTROL, VirtualKey::VK_F6]),
+            stood_still: Chord(vec![VirtualKey::VK_CONTROL, VirtualKey::VK_F7]),
+            max_reported: 50,
+            max_region_size: Some(256 * 1024 * 1024),
+            settings: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone)]
+pub struct PointerScanConfig {
+    /// Scan for static chains to the currently captured player pointer.
+    pub scan: Chord,
+    #[serde(default)]
+    pub settings: PointerScanSettings,
+}
+
+impl Default for PointerScanConfig {
+    fn default() -> Self {
+        Self {
+            scan: Chord(vec![VirtualKey::VK_CONTROL, VirtualKey::VK_F8]),
+            settings: Default::default(),
+        }
+    }
+}
+
+/// Load and validate the config in `directory`.
+///
+/// A config which can't be migrated or parsed is moved to a timestamped backup next to it, and the defaults are used in the
+/// meantime. The defaults aren't written to disk, a new config file is only created on the next start.
+pub fn load_config(directory: impl AsRef<Path>) -> eyre::Result<SkipConfig> {
+    let (path, format) = config_file(directory.as_ref())?;
+    let file = std::fs::read(&path).with_context(|| format!("Couldn't read the config at {path:?}"))?;
+    let parsed = migrations::migrate_file::<SkipConfig>(&path, file).and_then(|file| format.parse(&file));
+
+    let conf = match parsed {
+        Ok(conf) => conf,
+        Err(e) => {
+            let backup = backup_path(&path);
+            std::fs::rename(&path, &backup).context("Couldn't back up the broken config")?;
+            log::error!(
+                "{e}\nThe config was moved to {backup:?}, the default config is used until it has been fixed and \
+                moved back"
+            );
+            SkipConfig::default()
+        }
+    };
+
+    validate_config(&conf, &crate::plugins::plugin_names(directory.as_ref()))?;
+    Ok(conf)
+}
+
+/// Load and validate the config in `directory` during gameplay, unlike [load_config] a broken file is left untouched.
+pub fn reload_config(directory: impl AsRef<Path>) -> eyre::Result<SkipConfig> {
+    let (path, format) = config_file(directory.as_ref())?;
+    let file = std::fs::read(&path).with_context(|| format!("Couldn't read the config at {path:?}"))?;
+    let file = migrations::migrate_file::<SkipConfig>(&path, file)?;
+    let conf = format.parse(&file)?;
+
+    validate_config(&conf, &crate::plugins::plugin_names(directory.as_ref()))?;
+    Ok(conf)
+}
+
+/// The path and format of the config in `directory`, see [crate::format::find_file].
+pub fn config_file(directory: &Path) -> eyre::Result<(PathBuf, Format)> {
+    crate::format::find_file(directory, CONFIG_FILE_STEM).ok_or_else(|| {
+        let name = format!("{CONFIG_FILE_STEM}.{}", Format::ALL[0].extension());
+        eyre::eyre!("Couldn't find the config at {:?}", directory.join(name))
+    })
+}
+
+/// The settings which differ between `old` and `new`, as one `key: old -> new` line per setting.
+///
+/// # Example
+/// ```rust
+/// # use skip_core::config::{diff, SkipConfig};
+/// let old = SkipConfig::default();
+/// let new = SkipConfig { console: true, ..old.clone() };
+/// assert_eq!(diff(&old, &new), ["`console`: false -> true"]);
+/// ```
+pub fn diff<T: serde::Serialize>(old: &T, new: &T) -> Vec<String> {
+    fn diff_values(path: &str, old: &serde_json::Value, new: &serde_json::Value, changes: &mut Vec<String>) {
+        use serde_json::Value;
+
+        match (old, new) {
+            (Value::Object(old), Value::Object(new)) => {
+                let keys = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+                for key in keys {
+                    let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                    let old = old.get(key).unwrap_or(&Value::Null);
+                    let new = new.get(key).unwrap_or(&Value::Null);
+                    diff_values(&path, old, new, changes);
+                }
+            }
+            (old, new) if old != new => changes.push(format!("`{path}`: {old} -> {new}")),
+            _ => {}
+        }
+    }
+
+    let (Ok(old), Ok(new)) = (serde_json::to_value(old), serde_json::to_value(new)) else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    diff_values("", &old, &new, &mut changes);
+
+    changes
+}
+
+/// `skip_rs_config.json` -> `skip_rs_config.broken-<unix timestamp>.json`
+fn backup_path(path: &Path) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!("{stem}.broken-{timestamp}.{extension}"))
+}
+
+/// Write the default config if there is none yet, and (re)write the schemas of all files, see [crate::schema].
+///
+/// New configs are written as TOML, with the documentation of every setting as a comment. Existing configs in any other
+/// [Format] are kept as they are.
+pub fn create_initial_config(directory: impl AsRef<Path>) -> eyre::Result<()> {
+    std::fs::create_dir_all(directory.as_ref())
+        .with_context(|| format!("Couldn't create the config directory {:?}", directory.as_ref()))?;
+    if crate::format::find_file(directory.as_ref(), CONFIG_FILE_STEM).is_none() {
+        let path = directory
+            .as_ref()
+            .join(format!("{CONFIG_FILE_STEM}.{}", Format::Toml.extension()));
+        std::fs::write(path, crate::format::to_commented_toml(&SkipConfig::default())?)?;
+    }
+    schema::write_schemas(directory.as_ref())?;
+
+    Ok(())
+}
+
+/// Check the config for mistakes which would otherwise only show up as confusing behaviour in game.
+///
+/// `plugin_names` are the names of all available plugins, which the plugin selection settings are checked against.
+///
+/// # Errors
+///
+/// Lists every problem which was found.
+///
+/// # Example
+/// ```rust
+/// # use skip_core::config::{validate_config, SkipConfig};
+/// assert!(validate_config(&SkipConfig::default(), &[]).is_ok());
+/// ```
+pub fn validate_config(conf: &SkipConfig, plugin_names: &[String]) -> eyre::Result<()> {
+    let mut problems = Vec::new();
+
+    // Names include their backticks, as the names of bindings also mention their action.
+    let mut global = Vec::new();
+    global.extend(
+        conf.reload_config_keys
+            .iter()
+            .map(|keys| ("`reload_config_keys`".to_string(), keys)),
+    );
+    global.extend(
+        conf.switch_profile_keys
+            .iter()
+            .map(|keys| ("`switch_profile_keys`".to_string(), keys)),
+    );
+    if let Some(discovery) = &conf.discovery {
+        global.extend([
+            ("`discovery.new_scan`".to_string(), &discovery.new_scan),
+            ("`discovery.moved`".to_string(), &discovery.moved),
+            ("`discovery.stood_still`".to_string(), &discovery.stood_still),
+        ]);
+    }
+    global.extend(
+        conf.pointer_scan
+            .iter()
+            .map(|scan| ("`pointer_scan.scan`".to_string(), &scan.scan)),
+    );
+    check_chords(&global, global.len(), &mut problems);
+
+    // The keybinds of a profile are only active along with the global ones, not along with those of other profiles.
+    let profiles = std::iter::once(("", &conf.keybinds)).chain(
+        conf.profiles
+            .iter()
+            .filter_map(|(name, profile)| profile.keybinds.as_ref().map(|keybinds| (name.as_str(), keybinds))),
+    );
+    for (profile, keybinds) in profiles {
+        let prefix = if profile.is_empty() { String::new() } else { format!("profiles.{profile}.") };
+        let mut chords = Vec::new();
+        for (i, binding) in keybinds.iter().enumerate() {
+            let setting = format!("{prefix}keybinds[{i}]");
+            match Action::get(&binding.action) {
+                Some(action) => {
+                    if let Err(e) = action.validate(conf, &binding.params) {
+                        problems.push(format!("`{setting}` ({}): {e}", binding.action));
+                    }
+                }
+                None => problems.push(format!(
+                    "`{setting}.action` is the unknown action `{}`, available actions are: {}",
+                    binding.action,
+                    ACTIONS.iter().map(|action| action.name).collect::<Vec<_>>().join(", ")
+                )),
+            }
+            chords.push((format!("`{setting}` ({})", binding.action), &binding.keys));
+        }
+
+        let own = chords.len();
+        chords.extend(global.iter().cloned());
+        check_chords(&chords, own, &mut problems);
+    }
+
+    for name in conf.profiles.keys() {
+        if name == DEFAULT_PROFILE_NAME
+            || name.is_empty()
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            problems.push(format!(
+                "The profile name `{name}` has to consist of letters, digits, `_` and `-`, and can't be \
+                `{DEFAULT_PROFILE_NAME}`"
+            ));
+        }
+    }
+    if let Some(directory) = &conf.data_directory {
+        if let Err(e) = crate::paths::expand_env(directory) {
+            problems.push(format!("`data_directory`: {e}"));
+        }
+    }
+
+    let selection = &conf.plugins;
+    for (setting, name) in selection
+        .force
+        .iter()
+        .map(|name| ("plugins.force", name))
+        .chain(selection.disabled.iter().map(|name| ("plugins.disabled", name)))
+    {
+        if !plugin_names.contains(name) {
+            problems.push(format!(
+                "`{setting}` refers to the unknown plugin `{name}`, available plugins are: {}",
+                plugin_names.join(", ")
+            ));
+        }
+    }
+
+    let mut check = |valid: bool, setting: &str, expected: &str| {
+        if !valid {
+            problems.push(format!("`{setting}` has to be {expected}"));
+        }
+    };
+
+    if let Some(timeout) = conf.stale_pointer_timeout {
+        check(
+            timeout.is_finite() && timeout > 0.0,
+            "stale_pointer_timeout",
+            "a positive number, or null to disable it",
+        );
+    }
+    if let Some(deferred) = &conf.deferred_start {
+        check(
+            deferred.timeout.is_finite() && deferred.timeout >= 0.0,
+            "deferred_start.timeout",
+            "at least 0",
+        );
+        check(
+            deferred.max_interval.is_finite() && deferred.max_interval > 0.0,
+            "deferred_start.max_interval",
+            "a positive number",
+        );
+    }
+    if let Some(discovery) = &conf.discovery {
+        let settings = &discovery.settings;
+        check(discovery.max_reported > 0, "discovery.max_reported", "at least 1");
+        check(
+            discovery.max_region_size != Some(0),
+            "discovery.max_region_size",
+            "at least 1, or null for no limit",
+        );
+        check(settings.alignment > 0, "discovery.settings.alignment", "at least 1");
+        check(
+            settings.max_abs_value > 0.0,
+            "discovery.settings.max_abs_value",
+            "a positive number",
+        );
+        check(
+            settings.min_move_distance >= 0.0,
+            "discovery.settings.min_move_distance",
+            "at least 0",
+        );
+        check(
+            settings.max_move_distance > settings.min_move_distance,
+            "discovery.settings.max_move_distance",
+            "larger than `min_move_distance`",
+        );
+        check(
+            settings.stationary_epsilon >= 0.0,
+            "discovery.settings.stationary_epsilon",
+            "at least 0",
+        );
+        check(
+            settings.max_snapshot_size != Some(0),
+            "discovery.settings.max_snapshot_size",
+            "at least 1, or null for no limit",
+        );
+    }
+    if let Some(scan) = &conf.pointer_scan {
+        let settings = &scan.settings;
+        check(settings.max_depth > 0, "pointer_scan.settings.max_depth", "at least 1");
+        check(
+            settings.max_results > 0,
+            "pointer_scan.settings.max_results",
+            "at least 1",
+        );
+        check(
+            settings.max_nodes_per_level > 0,
+            "pointer_scan.settings.max_nodes_per_level",
+            "at least 1",
+        );
+    }
+
+    if !problems.is_empty() {
+        eyre::bail!("Invalid config:\n- {}", problems.join("\n- "));
+    }
+
+    Ok(())
+}
+
+/// Check the first `own` of the `chords` (named along with their backticks) for missing keys, and for overlapping with any of the chords after them.
+fn check_chords(chords: &[(String, &Chord)], own: usize, problems: &mut Vec<String>) {
+    for (i, (name, keys)) in chords.iter().enumerate().take(own) {
+        if keys.is_empty() {
+            problems.push(format!("{name} has no keys, it could never be triggered"));
+            continue;
+        }
+
+        let keys = keys.iter().collect::<BTreeSet<_>>();
+        for (other_name, other) in chords.iter().skip(i + 1).filter(|(_, other)| !other.is_empty()) {
+            let other = other.iter().collect::<BTreeSet<_>>();
+            if keys == other {
+                problems.push(format!("{name} and {other_name} use the same keys"));
+            } else if keys.is_subset(&other) {
+                problems.push(format!(
+                    "{name} is part of {other_name}, pressing {other_name} also triggers {name}"
+                ));
+            } else if other.is_subset(&keys) {
+                problems.push(format!(
+                    "{other_name} is part of {name}, pressing {name} also triggers {other_name}"
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config from before keybinds were a list, with keybinds in a profile as well, do not update it.
+    const CONFIG_V1: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/config_v1.json"));
+
+    fn problems(config: &SkipConfig) -> String {
+        validate_config(config, &[]).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        let config = SkipConfig::default();
+        assert!(diff(&config, &config.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_nested() {
+        let old = SkipConfig::default();
+        let mut new = old.clone();
+        new.console = true;
+        new.keybinds[0].keys = "F8".parse().unwrap();
+        new.deferred_start = None;
+        new.plugins.force = Some("Test".into());
+
+        assert_eq!(
+            diff(&old, &new),
+            [
+                "`console`: false -> true",
+                r#"`deferred_start`: {"max_interval":5.0,"timeout":120.0} -> null"#,
+                r#"`keybinds`: [{"action":"save_waypoint","keys":"F9"},{"action":"teleport_to_waypoint","keys":"F10"}] -> [{"action":"save_waypoint","keys":"F8"},{"action":"teleport_to_waypoint","keys":"F10"}]"#,
+                r#"`plugins.force`: null -> "Test""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_default() {
+        validate_config(&SkipConfig::default(), &[]).unwrap();
+    }
+
+    #[test]
+    fn validate_lists_every_problem() {
+        let mut config = SkipConfig::default();
+        config.keybinds[1].keys = config.keybinds[0].keys.clone();
+        config.plugins.force = Some("Unknown".into());
+        config.stale_pointer_timeout = Some(-1.0);
+        let error = problems(&config);
+
+        assert!(
+            error.contains("`keybinds[0]` (save_waypoint) and `keybinds[1]` (teleport_to_waypoint) use the same keys")
+        );
+        assert!(error.contains("`plugins.force`"));
+        assert!(error.contains("`stale_pointer_timeout`"));
+    }
+
+    #[test]
+    fn validate_overlapping_keys() {
+        let mut config = SkipConfig::default();
+        config.keybinds[1].keys = "R".parse().unwrap();
+
+        assert!(problems(&config)
+            .contains("pressing `reload_config_keys` also triggers `keybinds[1]` (teleport_to_waypoint)"));
+    }
+
+    #[test]
+    fn validate_params() {
+        // The same action can be bound several times, as long as its parameters are valid.
+        let mut config = SkipConfig::default();
+        let mut save_slot = KeyBinding::new("Shift+F1".parse().unwrap(), "save_waypoint");
+        save_slot.params = serde_json::json!({ "slot": 1 });
+        config.keybinds.push(save_slot.clone());
+        validate_config(&config, &[]).unwrap();
+
+        save_slot.params = serde_json::json!({ "slot": "first" });
+        config.keybinds.push(save_slot);
+        config.keybinds.push(KeyBinding::new("F11".parse().unwrap(), "fly"));
+        let error = problems(&config);
+
+        assert!(error.contains("`keybinds[3]` (save_waypoint): Invalid value for `params.slot`"));
+        assert!(error.contains("`keybinds[4].action` is the unknown action `fly`"));
+    }
+
+    #[test]
+    fn validate_profiles() {
+        // Profiles only have to be distinct from the global keybinds, not from the other profiles.
+        let mut config = SkipConfig::default();
+        let profile = ProfileConfig {
+            keybinds: Some(config.keybinds.clone()),
+        };
+        config.profiles.insert("any_percent".into(), profile.clone());
+        config.profiles.insert("all bosses".into(), profile);
+        config.switch_profile_keys = Some("F9".parse().unwrap());
+        let error = problems(&config);
+
+        assert!(error.contains(
+            "`profiles.any_percent.keybinds[0]` (save_waypoint) and `switch_profile_keys` use the same keys"
+        ));
+        assert!(error.contains("The profile name `all bosses`"));
+        assert!(!error.contains("(save_waypoint) and `profiles"));
+    }
+
+    #[test]
+    fn validate_plugin_selection() {
+        let mut config = SkipConfig::default();
+        config.plugins.force = Some("Known".into());
+        validate_config(&config, &["Known".to_string()]).unwrap();
+    }
+
+    fn load_broken(contents: &str) {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(format!("{CONFIG_FILE_STEM}.json"));
+        std::fs::write(&path, contents).unwrap();
+
+        let conf = load_config(directory.path()).unwrap();
+        assert!(diff(&conf, &SkipConfig::default()).is_empty());
+        assert!(!path.exists());
+
+        let backups = std::fs::read_dir(directory.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with(&format!("{CONFIG_FILE_STEM}.broken-")))
+            .collect::<Vec<_>>();
+        assert_eq!(backups.len(), 1, "{backups:?}");
+        let backup = directory.path().join(&backups[0]);
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), contents);
+    }
+
+    #[test]
+    fn load_invalid_version() {
+        load_broken(r#"{ "version": "2" }"#);
+    }
+
+    #[test]
+    fn load_future_version() {
+        load_broken(r#"{ "version": 99 }"#);
+    }
+
+    #[test]
+    fn load_v1_keybinds() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(format!("{CONFIG_FILE_STEM}.json"));
+        std::fs::write(&path, CONFIG_V1).unwrap();
+
+        let conf = load_config(directory.path()).unwrap();
+        assert_eq!(conf.version, SkipConfig::VERSION);
+        assert_eq!(conf.keybinds, SkipConfig::default().keybinds);
+        assert_eq!(
+            conf.profile_keybinds(Some("practice")),
+            [
+                KeyBinding::new("Ctrl+F9".parse().unwrap(), "save_waypoint"),
+                KeyBinding::new("Ctrl+F10".parse().unwrap(), "teleport_to_waypoint"),
+            ]
+        );
+        assert_eq!(conf.profile_keybinds(Some("route")), conf.keybinds);
+        assert_eq!(
+            std::fs::read_to_string(directory.path().join(format!("{CONFIG_FILE_STEM}.json.v1.bak"))).unwrap(),
+            CONFIG_V1
+        );
+    }
+}
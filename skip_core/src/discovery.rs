@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use std::sync::Arc;
+
+use crate::config::DiscoveryConfig;
+use crate::keys::KeyInput;
+use crate::platform::Platform;
+use crate::scanner::coordinates::{Candidate, CoordinateScanner, Movement};
+use crate::scanner::{MemorySource, ScanOptions};
+
+pub static DISCOVERY_RESULTS_FILE_NAME: &str = "skip_discovery_results.json";
+
+/// Hotkey driven "find my position" tool, for games which don't have a plugin yet.
+pub struct CoordinateDiscovery {
+    scanner: CoordinateScanner,
+    source: Box<dyn MemorySource>,
+    save_directory: PathBuf,
+    platform: Arc<dyn Platform>,
+}
+
+impl CoordinateDiscovery {
+    pub fn new(save_path: impl Into<PathBuf>, config: &DiscoveryConfig, platform: Arc<dyn Platform>) -> Self {
+        Self {
+            scanner: CoordinateScanner::new(config.settings.clone()),
+            source: platform.memory_source(Self::scan_options(config)),
+            save_directory: save_path.into(),
+            platform,
+        }
+    }
+
+    pub fn run(&mut self, config: &DiscoveryConfig, keyboard: &mut dyn KeyInput) -> eyre::Result<()> {
+        if keyboard.all_pressed(&config.new_scan) {
+            // Settings might've changed due to a config reload.
+            self.scanner = CoordinateScanner::new(config.settings.clone());
+            self.source = self.platform.memory_source(Self::scan_options(config));
+            let captured = self.scanner.snapshot(&*self.source);
+            log::info!("Started new coordinate scan, captured {captured} bytes. Now either move, or stand still");
+        }
+        if keyboard.all_pressed(&config.moved) {
+            self.narrow(config, Movement::Moved)?;
+        }
+        if keyboard.all_pressed(&config.stood_still) {
+            self.narrow(config, Movement::Stationary)?;
+        }
+
+        Ok(())
+    }
+
+    fn narrow(&mut self, config: &DiscoveryConfig, movement: Movement) -> eyre::Result<()> {
+        if !self.scanner.is_active() {
+            log::info!("No coordinate scan is in progress, start one first");
+            return Ok(());
+        }
+
+        let remaining = self.scanner.narrow(&*self.source, movement)?;
+        log::info!("Narrowed coordinate scan ({movement:?}), {remaining} candidates remain");
+
+        if remaining == 0 {
+            log::info!("No candidates are left, start a new scan to try again");
+            self.scanner.reset();
+        } else if remaining <= config.max_reported {
+            self.report(self.scanner.candidates())?;
+        }
+
+        Ok(())
+    }
+
+    fn scan_options(config: &DiscoveryConfig) -> ScanOptions {
+        ScanOptions {
+            max_region_size: config.max_region_size,
+            include_modules: false,
+        }
+    }
+
+    fn report(&self, candidates: &[Candidate]) -> eyre::Result<()> {
+        for candidate in candidates {
+            let [x, y, z] = candidate.value;
+            log::info!("Candidate at {:#X}: ({x}, {y}, {z})", candidate.address);
+        }
+
+        let save_file = self.save_directory.join(DISCOVERY_RESULTS_FILE_NAME);
+        let out = serde_json::to_string_pretty(candidates)?;
+        std::fs::write(&save_file, out)?;
+        log::info!("Saved {} candidates to {save_file:?}", candidates.len());
+
+        Ok(())
+    }
+}
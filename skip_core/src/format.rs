@@ -0,0 +1,406 @@
+//! The file formats in which the config and plugin configs can be written, picked by the file's extension.
+//!
+//! All formats are deserialized into the same types, so they're validated the same way. JSON5 and TOML allow comments,
+//! which makes them a better fit for annotated plugin configs.
+//!
+//! # Example
+//! ```rust
+//! # use skip_core::format::Format;
+//! # use skip_core::plugins::PlayerCoordinates;
+//! let toml = "x = 1.0\ny = 2.0\nz = 3.0";
+//! let json = r#"{ "x": 1.0, "y": 2.0, "z": 3.0 }"#;
+//! assert_eq!(
+//!     Format::Toml.parse::<PlayerCoordinates>(toml.as_bytes()).unwrap(),
+//!     Format::Json.parse::<PlayerCoordinates>(json.as_bytes()).unwrap()
+//! );
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use schemars::schema::{ObjectValidation, RootSchema, Schema, SchemaObject, SingleOrVec};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::schema::{SchemaFile, SCHEMA_DIRECTORY_NAME};
+
+const COMMENT_WIDTH: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Json5,
+    Toml,
+}
+
+impl Format {
+    /// All formats, in the order in which they're preferred if a file exists in multiple formats.
+    pub const ALL: [Format; 3] = [Format::Toml, Format::Json5, Format::Json];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Json5 => "json5",
+            Format::Toml => "toml",
+        }
+    }
+
+    /// Whether files in this format can contain comments, which are lost when a file is rewritten.
+    pub fn has_comments(self) -> bool {
+        self != Format::Json
+    }
+
+    /// The format of the file at `path`, based on its extension.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+
+    /// Deserialize `data` with errors pointing at the offending key, unknown keys are ignored with a warning.
+    ///
+    /// The `$schema` key is allowed in every document, see [crate::schema].
+    pub fn parse<T: DeserializeOwned>(self, data: &[u8]) -> eyre::Result<T> {
+        let text = std::str::from_utf8(data)?;
+        let json5 = |text| json5::Deserializer::from_str(text).map_err(|e| (".".to_string(), e.to_string()));
+
+        deserialize_reported(self, |on_unknown| match self {
+            Format::Json => parse_with(&mut serde_json::Deserializer::from_str(text), on_unknown),
+            Format::Json5 => parse_with(&mut json5(text)?, on_unknown),
+            Format::Toml => parse_with(toml::Deserializer::new(text), on_unknown),
+        })
+    }
+
+    /// Deserialize `data` without any checks, for handling documents before they're deserialized into their type.
+    pub fn parse_value(self, data: &[u8]) -> eyre::Result<Value> {
+        let text = std::str::from_utf8(data)?;
+        Ok(match self {
+            Format::Json => serde_json::from_str(text)?,
+            Format::Json5 => json5::from_str(text)?,
+            Format::Toml => toml::from_str(text)?,
+        })
+    }
+
+    /// Serialize `value` in this format.
+    ///
+    /// TOML has no `null`, fields which are [None] are left out.
+    pub fn to_string<T: Serialize>(self, value: &T) -> eyre::Result<String> {
+        Ok(match self {
+            // JSON is valid JSON5
+            Format::Json | Format::Json5 => serde_json::to_string_pretty(value)?,
+            Format::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Json => "JSON",
+            Format::Json5 => "JSON5",
+            Format::Toml => "TOML",
+        })
+    }
+}
+
+/// Find the file called `stem` with any of the supported extensions in `directory`.
+///
+/// If the file exists in multiple formats the first in [Format::ALL] is used and the others are reported.
+pub fn find_file(directory: &Path, stem: &str) -> Option<(PathBuf, Format)> {
+    let mut existing = Format::ALL
+        .into_iter()
+        .map(|format| (directory.join(format!("{stem}.{}", format.extension())), format))
+        .filter(|(path, _)| path.is_file());
+
+    let found = existing.next()?;
+    for (ignored, _) in existing {
+        log::warn!("Both {:?} and {ignored:?} exist, only the former is used", found.0);
+    }
+
+    Some(found)
+}
+
+/// Serialize `value` as TOML, with the description of every field in the schema (its doc comment) as a comment above
+/// it, and a reference to the schema for editors supporting it.
+///
+/// # Example
+/// ```rust
+/// # use skip_core::config::SkipConfig;
+/// # use skip_core::format::to_commented_toml;
+/// let toml = to_commented_toml(&SkipConfig::default()).unwrap();
+/// assert!(toml.contains("# Whether to open a console for logging\nconsole = false"));
+/// ```
+pub fn to_commented_toml<T: SchemaFile + Serialize>(value: &T) -> eyre::Result<String> {
+    let mut document = toml_edit::DocumentMut::from_str(&Format::Toml.to_string(value)?)?;
+    let schema = T::schema();
+    comment_table(document.as_table_mut(), &schema.schema, &schema);
+
+    Ok(format!(
+        "#:schema ./{SCHEMA_DIRECTORY_NAME}/{}\n\n{document}",
+        T::SCHEMA_FILE_NAME
+    ))
+}
+
+/// Deserialize an already parsed (and possibly modified) document, with the same error reporting as [Format::parse].
+pub fn from_value<T: DeserializeOwned>(value: Value) -> eyre::Result<T> {
+    deserialize_reported("document", |on_unknown| parse_with(value, on_unknown))
+}
+
+/// Run `deserialize` with errors pointing at the offending key, and warn about unknown keys other than `$schema`.
+fn deserialize_reported<T>(
+    what: impl Display,
+    deserialize: impl FnOnce(&mut dyn FnMut(serde_ignored::Path)) -> Result<T, (String, String)>,
+) -> eyre::Result<T> {
+    let mut unknown = Vec::new();
+    let mut on_unknown = |path: serde_ignored::Path| {
+        let path = path.to_string();
+        if path != "$schema" {
+            unknown.push(path);
+        }
+    };
+
+    let result = deserialize(&mut on_unknown).map_err(|(key, e)| {
+        if key == "." {
+            eyre::eyre!("Invalid {what}: {e}")
+        } else {
+            eyre::eyre!("Invalid value for `{key}`: {e}")
+        }
+    })?;
+
+    for key in unknown {
+        log::warn!("Ignoring unknown key `{key}`, it might be misspelled or no longer supported");
+    }
+
+    Ok(result)
+}
+
+/// Deserialize `T` with serde_path_to_error and serde_ignored, errors are returned along with the path of the key.
+fn parse_with<'de, T, D>(deserializer: D, on_unknown: impl FnMut(serde_ignored::Path)) -> Result<T, (String, String)>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let mut on_unknown = on_unknown;
+    let deserializer = serde_ignored::Deserializer::new(deserializer, &mut on_unknown);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|e| (e.path().to_string(), e.into_inner().to_string()))
+}
+
+fn comment_table(table: &mut toml_edit::Table, schema: &SchemaObject, root: &RootSchema) {
+    let Some(object) = object_schema(schema, root) else {
+        return;
+    };
+
+    for (mut key, item) in table.iter_mut() {
+        let Some(Schema::Object(field)) = object.properties.get(key.get()) else {
+            continue;
+        };
+        let description = field.metadata.as_ref().and_then(|meta| meta.description.as_deref());
+        let comment = description.map(to_comment);
+
+        match item {
+            toml_edit::Item::Table(table) => {
+                if let Some(comment) = comment {
+                    table.decor_mut().set_prefix(format!("\n{comment}"));
+                }
+                comment_table(table, field, root);
+            }
+            // Only the first entry is commented, repeating the comments for every entry would bury the values.
+            toml_edit::Item::ArrayOfTables(array) => {
+                let Some(first) = array.get_mut(0) else {
+                    continue;
+                };
+                if let Some(comment) = comment {
+                    first.decor_mut().set_prefix(format!("\n{comment}"));
+                }
+                if let Some(items) = array_items(field) {
+                    comment_table(first, items, root);
+                }
+            }
+            _ => {
+                if let Some(comment) = comment {
+                    key.leaf_decor_mut().set_prefix(comment);
+                }
+            }
+        }
+    }
+}
+
+/// Turn a description into `#` comment lines, wrapped to at most [COMMENT_WIDTH] characters.
+fn to_comment(description: &str) -> String {
+    let mut comment = String::new();
+    for paragraph in description.lines() {
+        let mut line = String::from("#");
+        for word in paragraph.split_whitespace() {
+            if line.len() > 1 && line.len() + 1 + word.len() > COMMENT_WIDTH {
+                comment.push_str(&line);
+                comment.push('\n');
+                line = String::from("#");
+            }
+            line.push(' ');
+            line.push_str(word);
+        }
+        comment.push_str(&line);
+        comment.push('\n');
+    }
+
+    comment
+}
+
+/// The schema of the items of the array schema `schema`, if they all share one.
+fn array_items(schema: &SchemaObject) -> Option<&SchemaObject> {
+    match schema.array.as_ref()?.items.as_ref()? {
+        SingleOrVec::Single(items) => match items.as_ref() {
+            Schema::Object(items) => Some(items),
+            Schema::Bool(_) => None,
+        },
+        SingleOrVec::Vec(_) => None,
+    }
+}
+
+/// The object schema `schema` refers to, looking through references and `Option`s.
+fn object_schema<'a>(schema: &'a SchemaObject, root: &'a RootSchema) -> Option<&'a ObjectValidation> {
+    if let Some(reference) = &schema.reference {
+        let name = reference.strip_prefix("#/definitions/")?;
+        return match root.definitions.get(name)? {
+            Schema::Object(definition) => object_schema(definition, root),
+            Schema::Bool(_) => None,
+        };
+    }
+    if let Some(object) = &schema.object {
+        return Some(object);
+    }
+
+    let subschemas = schema.subschemas.as_ref()?;
+    subschemas
+        .all_of
+        .iter()
+        .chain(&subschemas.any_of)
+        .flatten()
+        .find_map(|schema| match schema {
+            Schema::Object(schema) => object_schema(schema, root),
+            Schema::Bool(_) => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SkipConfig;
+    use crate::plugins::generic::GenericConfig;
+
+    const JSON: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../example_configs/skip_runback_plugin_SEKIRO.json"
+    ));
+    const TOML: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../example_configs/skip_runback_plugin_SEKIRO.toml"
+    ));
+    const JSON5: &str = r#"{
+        version: 1,
+        // Found by someone, for game version 1.06
+        identifiers: { plugin_name: "Generic Skip Sekiro Example", expected_module: "sekiro.exe", expected_exe_name: "sekiro.exe" },
+        position: { InterceptPtr: { intercept_signature: "0F 28 81 80 00 00 00 4D", register: "Rcx", filter: null } },
+        pointer_offsets: { x: 0x80, y: "0x84", z: 136 },
+    }"#;
+
+    #[test]
+    fn formats_are_equivalent() {
+        let expected: GenericConfig = Format::Json.parse(JSON.as_bytes()).unwrap();
+        assert_eq!(Format::Toml.parse::<GenericConfig>(TOML.as_bytes()).unwrap(), expected);
+        assert_eq!(
+            Format::Json5.parse::<GenericConfig>(JSON5.as_bytes()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let expected: GenericConfig = Format::Json.parse(JSON.as_bytes()).unwrap();
+        for format in Format::ALL {
+            let written = format.to_string(&expected).unwrap();
+            assert_eq!(
+                format.parse::<GenericConfig>(written.as_bytes()).unwrap(),
+                expected,
+                "{format}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_reports_key() {
+        let toml = TOML.replace(r#"x = "0x80""#, r#"x = "far""#);
+        let error = Format::Toml
+            .parse::<GenericConfig>(toml.as_bytes())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`pointer_offsets.x`"), "{error}");
+
+        let error = Format::Json5.parse::<GenericConfig>(b"{").unwrap_err().to_string();
+        assert!(error.starts_with("Invalid JSON5"), "{error}");
+    }
+
+    #[test]
+    fn parse_ignores_unknown_keys() {
+        let mut json: Value = serde_json::from_str(JSON).unwrap();
+        json["$schema"] = "./schemas/plugin.schema.json".into();
+        json["pointer_offsets"]["w"] = 0.into();
+
+        let parsed = from_value::<GenericConfig>(json).unwrap();
+        assert_eq!(parsed, Format::Json.parse(JSON.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn from_path() {
+        assert_eq!(Format::from_path(Path::new("a/config.TOML")), Some(Format::Toml));
+        assert_eq!(Format::from_path(Path::new("config.json5")), Some(Format::Json5));
+        assert_eq!(Format::from_path(Path::new("config.json.v0.bak")), None);
+        assert_eq!(Format::from_path(Path::new("config")), None);
+    }
+
+    #[test]
+    fn find_file_prefers_toml() {
+        let directory = tempfile::tempdir().unwrap();
+        assert_eq!(find_file(directory.path(), "config"), None);
+
+        std::fs::write(directory.path().join("config.json"), "{}").unwrap();
+        assert_eq!(
+            find_file(directory.path(), "config"),
+            Some((directory.path().join("config.json"), Format::Json))
+        );
+
+        std::fs::write(directory.path().join("config.toml"), "").unwrap();
+        assert_eq!(
+            find_file(directory.path(), "config"),
+            Some((directory.path().join("config.toml"), Format::Toml))
+        );
+    }
+
+    #[test]
+    fn commented_toml() {
+        let toml = to_commented_toml(&SkipConfig::default()).unwrap();
+        assert!(toml.starts_with("#:schema ./schemas/skip_rs_config.schema.json\n"));
+        assert!(toml.contains("# Whether to open a console for logging\nconsole = false"));
+
+        let parsed: SkipConfig = Format::Toml.parse(toml.as_bytes()).unwrap();
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            serde_json::to_value(SkipConfig::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn comments_are_wrapped() {
+        let description = format!("{}\nSecond paragraph", "word ".repeat(40));
+        let comment = to_comment(&description);
+
+        assert!(comment
+            .lines()
+            .all(|line| line.len() <= COMMENT_WIDTH && line.starts_with("# ")));
+        assert_eq!(comment.lines().last(), Some("# Second paragraph"));
+    }
+}
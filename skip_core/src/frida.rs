@@ -0,0 +1,84 @@
+//! Hooks through `frida`, for hosts which run inside the game process.
+
+use std::ffi::c_void;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use frida_gum::interceptor::{InvocationContext, Listener, ProbeListener};
+use frida_gum::{CpuContext, NativePointer};
+use once_cell::sync::Lazy;
+
+use crate::platform::{HitListener, Hook};
+use crate::plugins::context::RegisterContext;
+use crate::plugins::generic::Register;
+use crate::utils::NullLock;
+
+pub static GUM: Lazy<frida_gum::Gum> = Lazy::new(|| unsafe { frida_gum::Gum::obtain() });
+pub static PROBE_INTERCEPTOR: Lazy<Mutex<NullLock<frida_gum::interceptor::Interceptor>>> =
+    Lazy::new(|| Mutex::new(NullLock(frida_gum::interceptor::Interceptor::obtain(&GUM))));
+
+/// Attach `listener` to the instruction at `address` in the current process, see [crate::platform::Platform::attach].
+pub fn attach(address: usize, listener: Box<dyn HitListener>) -> eyre::Result<Hook> {
+    // Undocumented in `attach_instruction`, but this *needs* to be pinned as they save the pointer we pass
+    let mut probe = Box::pin(FridaListener(listener));
+    let listener = PROBE_INTERCEPTOR
+        .lock()
+        .unwrap()
+        .attach_instruction(NativePointer(address as *mut c_void), &mut *probe)
+        .map_err(|e| eyre::eyre!("Failed to attach interceptor at {address:#X}: {e:?}"))?;
+
+    Ok(Hook::new(FridaHook {
+        listener: Some(listener),
+        _probe: probe,
+    }))
+}
+
+/// Forwards `frida` probe hits to a platform independent [HitListener].
+struct FridaListener(Box<dyn HitListener>);
+
+impl ProbeListener for FridaListener {
+    fn on_hit(&mut self, context: InvocationContext) {
+        self.0.on_hit(&FridaContext(context.cpu_context()));
+    }
+}
+
+/// Keeps the probe alive while attached, and detaches it when dropped.
+struct FridaHook {
+    listener: Option<Listener>,
+    _probe: Pin<Box<FridaListener>>,
+}
+
+impl Drop for FridaHook {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            PROBE_INTERCEPTOR.lock().unwrap().detach(listener);
+        }
+    }
+}
+
+struct FridaContext<'a>(CpuContext<'a>);
+
+impl RegisterContext for FridaContext<'_> {
+    fn register(&self, register: Register) -> u64 {
+        let ctx = &self.0;
+        match register {
+            Register::Rax => ctx.rax(),
+            Register::Rbx => ctx.rbx(),
+            Register::Rcx => ctx.rcx(),
+            Register::Rdx => ctx.rdx(),
+            Register::Rsi => ctx.rsi(),
+            Register::Rdi => ctx.rdi(),
+            Register::Rbp => ctx.rbp(),
+            Register::Rsp => ctx.rsp(),
+            Register::R8 => ctx.r8(),
+            Register::R9 => ctx.r9(),
+            Register::R10 => ctx.r10(),
+            Register::R11 => ctx.r11(),
+            Register::R12 => ctx.r12(),
+            Register::R13 => ctx.r13(),
+            Register::R14 => ctx.r14(),
+            Register::R15 => ctx.r15(),
+            Register::Rip => ctx.rip(),
+        }
+    }
+}
@@ -0,0 +1,540 @@
+//! Platform independent keyboard keys.
+//!
+//! Keys are identified by their Windows virtual key code, hosts translate these to whatever their input source uses.
+//! In the config the keys held together form a [Chord], written like `"Ctrl+Shift+F9"`. Lists of `VK_*` names like
+//! `["VK_CONTROL", "VK_SHIFT", "VK_F9"]` are accepted as well, for compatibility with existing configs.
+//!
+//! # Example
+//! ```rust
+//! # use skip_core::keys::{Chord, VirtualKey};
+//! let chord: Chord = "Ctrl+Shift+F9".parse().unwrap();
+//! assert_eq!(*chord, [VirtualKey::VK_CONTROL, VirtualKey::VK_SHIFT, VirtualKey::VK_F9]);
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, SubschemaValidation};
+use schemars::JsonSchema;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! virtual_keys {
+    ($($name:ident = $code:literal,)*) => {
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+        pub enum VirtualKey {
+            $($name,)*
+        }
+
+        impl VirtualKey {
+            /// All known keys.
+            pub const ALL: &'static [VirtualKey] = &[$(VirtualKey::$name,)*];
+
+            /// The Windows virtual key code of this key.
+            pub const fn code(self) -> u16 {
+                match self {
+                    $(VirtualKey::$name => $code,)*
+                }
+            }
+
+            /// The `VK_*` name of this key.
+            pub const fn vk_name(self) -> &'static str {
+                match self {
+                    $(VirtualKey::$name => stringify!($name),)*
+                }
+            }
+
+            pub fn from_code(code: u16) -> Option<Self> {
+                match code {
+                    $($code => Some(VirtualKey::$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+virtual_keys! {
+    VK_LBUTTON = 0x01,
+    VK_RBUTTON = 0x02,
+    VK_CANCEL = 0x03,
+    VK_MBUTTON = 0x04,
+    VK_XBUTTON1 = 0x05,
+    VK_XBUTTON2 = 0x06,
+    VK_BACK = 0x08,
+    VK_TAB = 0x09,
+    VK_CLEAR = 0x0C,
+    VK_RETURN = 0x0D,
+    VK_SHIFT = 0x10,
+    VK_CONTROL = 0x11,
+    VK_MENU = 0x12,
+    VK_PAUSE = 0x13,
+    VK_CAPITAL = 0x14,
+    VK_KANA = 0x15,
+    VK_IME_ON = 0x16,
+    VK_JUNJA = 0x17,
+    VK_FINAL = 0x18,
+    VK_HANJA = 0x19,
+    VK_IME_OFF = 0x1A,
+    VK_ESCAPE = 0x1B,
+    VK_CONVERT = 0x1C,
+    VK_NONCONVERT = 0x1D,
+    VK_ACCEPT = 0x1E,
+    VK_MODECHANGE = 0x1F,
+    VK_SPACE = 0x20,
+    VK_PRIOR = 0x21,
+    VK_NEXT = 0x22,
+    VK_END = 0x23,
+    VK_HOME = 0x24,
+    VK_LEFT = 0x25,
+    VK_UP = 0x26,
+    VK_RIGHT = 0x27,
+    VK_DOWN = 0x28,
+    VK_SELECT = 0x29,
+    VK_PRINT = 0x2A,
+    VK_EXECUTE = 0x2B,
+    VK_SNAPSHOT = 0x2C,
+    VK_INSERT = 0x2D,
+    VK_DELETE = 0x2E,
+    VK_HELP = 0x2F,
+    VK_0 = 0x30,
+    VK_1 = 0x31,
+    VK_2 = 0x32,
+    VK_3 = 0x33,
+    VK_4 = 0x34,
+    VK_5 = 0x35,
+    VK_6 = 0x36,
+    VK_7 = 0x37,
+    VK_8 = 0x38,
+    VK_9 = 0x39,
+    VK_A = 0x41,
+    VK_B = 0x42,
+    VK_C = 0x43,
+    VK_D = 0x44,
+    VK_E = 0x45,
+    VK_F = 0x46,
+    VK_G = 0x47,
+    VK_H = 0x48,
+    VK_I = 0x49,
+    VK_J = 0x4A,
+    VK_K = 0x4B,
+    VK_L = 0x4C,
+    VK_M = 0x4D,
+    VK_N = 0x4E,
+    VK_O = 0x4F,
+    VK_P = 0x50,
+    VK_Q = 0x51,
+    VK_R = 0x52,
+    VK_S = 0x53,
+    VK_T = 0x54,
+    VK_U = 0x55,
+    VK_V = 0x56,
+    VK_W = 0x57,
+    VK_X = 0x58,
+    VK_Y = 0x59,
+    VK_Z = 0x5A,
+    VK_LWIN = 0x5B,
+    VK_RWIN = 0x5C,
+    VK_APPS = 0x5D,
+    VK_SLEEP = 0x5F,
+    VK_NUMPAD0 = 0x60,
+    VK_NUMPAD1 = 0x61,
+    VK_NUMPAD2 = 0x62,
+    VK_NUMPAD3 = 0x63,
+    VK_NUMPAD4 = 0x64,
+    VK_NUMPAD5 = 0x65,
+    VK_NUMPAD6 = 0x66,
+    VK_NUMPAD7 = 0x67,
+    VK_NUMPAD8 = 0x68,
+    VK_NUMPAD9 = 0x69,
+    VK_MULTIPLY = 0x6A,
+    VK_ADD = 0x6B,
+    VK_SEPARATOR = 0x6C,
+    VK_SUBTRACT = 0x6D,
+    VK_DECIMAL = 0x6E,
+    VK_DIVIDE = 0x6F,
+    VK_F1 = 0x70,
+    VK_F2 = 0x71,
+    VK_F3 = 0x72,
+    VK_F4 = 0x73,
+    VK_F5 = 0x74,
+    VK_F6 = 0x75,
+    VK_F7 = 0x76,
+    VK_F8 = 0x77,
+    VK_F9 = 0x78,
+    VK_F10 = 0x79,
+    VK_F11 = 0x7A,
+    VK_F12 = 0x7B,
+    VK_F13 = 0x7C,
+    VK_F14 = 0x7D,
+    VK_F15 = 0x7E,
+    VK_F16 = 0x7F,
+    VK_F17 = 0x80,
+    VK_F18 = 0x81,
+    VK_F19 = 0x82,
+    VK_F20 = 0x83,
+    VK_F21 = 0x84,
+    VK_F22 = 0x85,
+    VK_F23 = 0x86,
+    VK_F24 = 0x87,
+    VK_NUMLOCK = 0x90,
+    VK_SCROLL = 0x91,
+    VK_LSHIFT = 0xA0,
+    VK_RSHIFT = 0xA1,
+    VK_LCONTROL = 0xA2,
+    VK_RCONTROL = 0xA3,
+    VK_LMENU = 0xA4,
+    VK_RMENU = 0xA5,
+    VK_BROWSER_BACK = 0xA6,
+    VK_BROWSER_FORWARD = 0xA7,
+    VK_BROWSER_REFRESH = 0xA8,
+    VK_BROWSER_STOP = 0xA9,
+    VK_BROWSER_SEARCH = 0xAA,
+    VK_BROWSER_FAVORITES = 0xAB,
+    VK_BROWSER_HOME = 0xAC,
+    VK_VOLUME_MUTE = 0xAD,
+    VK_VOLUME_DOWN = 0xAE,
+    VK_VOLUME_UP = 0xAF,
+    VK_MEDIA_NEXT_TRACK = 0xB0,
+    VK_MEDIA_PREV_TRACK = 0xB1,
+    VK_MEDIA_STOP = 0xB2,
+    VK_MEDIA_PLAY_PAUSE = 0xB3,
+    VK_LAUNCH_MAIL = 0xB4,
+    VK_LAUNCH_MEDIA_SELECT = 0xB5,
+    VK_LAUNCH_APP1 = 0xB6,
+    VK_LAUNCH_APP2 = 0xB7,
+    VK_OEM_1 = 0xBA,
+    VK_OEM_PLUS = 0xBB,
+    VK_OEM_COMMA = 0xBC,
+    VK_OEM_MINUS = 0xBD,
+    VK_OEM_PERIOD = 0xBE,
+    VK_OEM_2 = 0xBF,
+    VK_OEM_3 = 0xC0,
+    VK_OEM_4 = 0xDB,
+    VK_OEM_5 = 0xDC,
+    VK_OEM_6 = 0xDD,
+    VK_OEM_7 = 0xDE,
+    VK_OEM_8 = 0xDF,
+    VK_OEM_102 = 0xE2,
+    VK_PROCESSKEY = 0xE5,
+    VK_PACKET = 0xE7,
+    VK_ATTN = 0xF6,
+    VK_CRSEL = 0xF7,
+    VK_EXSEL = 0xF8,
+    VK_EREOF = 0xF9,
+    VK_PLAY = 0xFA,
+    VK_ZOOM = 0xFB,
+    VK_NONAME = 0xFC,
+    VK_PA1 = 0xFD,
+    VK_OEM_CLEAR = 0xFE,
+}
+
+/// Readable names for keys whose `VK_*` name isn't readable by itself, the first name of a key is the one written.
+///
+/// Other keys are named by their `VK_*` name without the prefix, e.g. `A`, `5` or `F9`.
+static KEY_NAMES: &[(VirtualKey, &[&str])] = {
+    use VirtualKey::*;
+    &[
+        (VK_CONTROL, &["Ctrl", "Control"]),
+        (VK_SHIFT, &["Shift"]),
+        (VK_MENU, &["Alt"]),
+        (VK_LCONTROL, &["LCtrl", "LControl"]),
+        (VK_RCONTROL, &["RCtrl", "RControl"]),
+        (VK_LSHIFT, &["LShift"]),
+        (VK_RSHIFT, &["RShift"]),
+        (VK_LMENU, &["LAlt"]),
+        (VK_RMENU, &["RAlt", "AltGr"]),
+        (VK_LWIN, &["LWin"]),
+        (VK_RWIN, &["RWin"]),
+        (VK_LBUTTON, &["MouseLeft"]),
+        (VK_RBUTTON, &["MouseRight"]),
+        (VK_MBUTTON, &["MouseMiddle"]),
+        (VK_XBUTTON1, &["Mouse4"]),
+        (VK_XBUTTON2, &["Mouse5"]),
+        (VK_BACK, &["Backspace"]),
+        (VK_RETURN, &["Enter", "Return"]),
+        (VK_ESCAPE, &["Esc", "Escape"]),
+        (VK_CAPITAL, &["CapsLock"]),
+        (VK_PRIOR, &["PageUp", "PgUp"]),
+        (VK_NEXT, &["PageDown", "PgDn"]),
+        (VK_INSERT, &["Insert", "Ins"]),
+        (VK_DELETE, &["Delete", "Del"]),
+        (VK_SNAPSHOT, &["PrintScreen", "PrtSc"]),
+        (VK_NUMLOCK, &["NumLock"]),
+        (VK_SCROLL, &["ScrollLock"]),
+        (VK_NUMPAD0, &["Num0", "Numpad0"]),
+        (VK_NUMPAD1, &["Num1", "Numpad1"]),
+        (VK_NUMPAD2, &["Num2", "Numpad2"]),
+        (VK_NUMPAD3, &["Num3", "Numpad3"]),
+        (VK_NUMPAD4, &["Num4", "Numpad4"]),
+        (VK_NUMPAD5, &["Num5", "Numpad5"]),
+        (VK_NUMPAD6, &["Num6", "Numpad6"]),
+        (VK_NUMPAD7, &["Num7", "Numpad7"]),
+        (VK_NUMPAD8, &["Num8", "Numpad8"]),
+        (VK_NUMPAD9, &["Num9", "Numpad9"]),
+        (VK_MULTIPLY, &["NumMultiply"]),
+        (VK_ADD, &["NumAdd"]),
+        (VK_SUBTRACT, &["NumSubtract"]),
+        (VK_DECIMAL, &["NumDecimal"]),
+        (VK_DIVIDE, &["NumDivide"]),
+        (VK_OEM_PLUS, &["Plus"]),
+        (VK_OEM_MINUS, &["Minus"]),
+        (VK_OEM_COMMA, &["Comma"]),
+        (VK_OEM_PERIOD, &["Period"]),
+    ]
+};
+
+impl VirtualKey {
+    /// All names this key can be written as, the first one being the one it's written as.
+    fn names(self) -> impl Iterator<Item = &'static str> {
+        let readable = KEY_NAMES
+            .iter()
+            .find(|(key, _)| *key == self)
+            .map(|(_, names)| *names)
+            .unwrap_or_default();
+        let short = self.vk_name().trim_start_matches("VK_");
+
+        readable.iter().copied().chain([short, self.vk_name()])
+    }
+}
+
+impl Display for VirtualKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.names().next().unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KeyParseError {
+    #[error("Unknown key `{name}`{hint}")]
+    Unknown { name: String, hint: String },
+    #[error("Missing a key in `{0}`, the + key is called `Plus`")]
+    Missing(String),
+}
+
+impl FromStr for VirtualKey {
+    type Err = KeyParseError;
+
+    /// Parse any of the names of a key, ignoring case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.trim();
+        if let Some(key) = VirtualKey::ALL
+            .iter()
+            .find(|key| key.names().any(|known| known.eq_ignore_ascii_case(name)))
+        {
+            return Ok(*key);
+        }
+
+        let closest = VirtualKey::ALL
+            .iter()
+            .flat_map(|key| key.names().map(move |known| (edit_distance(known, name), key)))
+            .min_by_key(|(distance, _)| *distance)
+            .filter(|(distance, _)| *distance <= (name.len() / 3).max(1));
+        let hint = match closest {
+            Some((_, key)) => format!(", did you mean `{key}`?"),
+            None => ", keys are written like `Ctrl`, `Alt`, `A`, `5`, `F9` or `Num1`".to_string(),
+        };
+
+        Err(KeyParseError::Unknown {
+            name: name.to_string(),
+            hint,
+        })
+    }
+}
+
+/// The number of single character edits (including swapping two neighbouring characters) to get from `a` to `b`,
+/// ignoring case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_ascii_lowercase().into_bytes();
+    let b = b.to_ascii_lowercase().into_bytes();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution.min(distances[i - 1][j] + 1).min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Keys which have to be held together, written as their names joined by `+`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Chord(pub Vec<VirtualKey>);
+
+impl Deref for Chord {
+    type Target = [VirtualKey];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Chord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("+")?;
+            }
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Chord {
+    type Err = KeyParseError;
+
+    /// Parse keys joined by `+`, an empty string is a chord without keys.
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        if chord.trim().is_empty() {
+            return Ok(Chord::default());
+        }
+
+        chord
+            .split('+')
+            .map(|name| match name.trim() {
+                "" => Err(KeyParseError::Missing(chord.to_string())),
+                name => name.parse(),
+            })
+            .collect::<Result<_, _>>()
+            .map(Chord)
+    }
+}
+
+impl Serialize for Chord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ChordVisitor)
+    }
+}
+
+struct ChordVisitor;
+
+impl<'de> Visitor<'de> for ChordVisitor {
+    type Value = Chord;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("keys joined by `+` like \"Ctrl+Shift+F9\", or a list of keys")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut keys = Vec::new();
+        while let Some(name) = seq.next_element::<String>()? {
+            keys.push(name.parse().map_err(A::Error::custom)?);
+        }
+        Ok(Chord(keys))
+    }
+}
+
+impl JsonSchema for Chord {
+    fn schema_name() -> String {
+        "Chord".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let text = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                description: Some("Key names joined by `+`, e.g. `Ctrl+Shift+F9` or `Alt+Num1`".into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![text.into(), gen.subschema_for::<Vec<VirtualKey>>()]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// A source of keyboard input, implemented by each host.
+pub trait KeyInput {
+    /// Whether the chord formed by `keys` was pressed, i.e. all `keys` are held and at least one of them was pressed
+    /// this frame.
+    fn all_pressed(&mut self, keys: &[VirtualKey]) -> bool;
+
+    /// Called at the end of every frame of the app loop.
+    fn end_frame(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chord_round_trip() {
+        let chord: Chord = serde_json::from_str(r#""Ctrl+Shift+F9""#).unwrap();
+        assert_eq!(
+            *chord,
+            [VirtualKey::VK_CONTROL, VirtualKey::VK_SHIFT, VirtualKey::VK_F9]
+        );
+        assert_eq!(serde_json::to_string(&chord).unwrap(), r#""Ctrl+Shift+F9""#);
+    }
+
+    #[test]
+    fn chord_names() {
+        let legacy: Chord = serde_json::from_str(r#"["VK_MENU", "VK_NUMPAD1"]"#).unwrap();
+        assert_eq!(legacy, "alt + num1".parse().unwrap());
+        assert_eq!(legacy, "Menu+NUMPAD1".parse().unwrap());
+        assert_eq!(legacy.to_string(), "Alt+Num1");
+        assert_eq!("".parse::<Chord>().unwrap(), Chord::default());
+    }
+
+    #[test]
+    fn every_key_round_trips() {
+        for key in VirtualKey::ALL {
+            assert_eq!(key.to_string().parse::<VirtualKey>().unwrap(), *key);
+            assert_eq!(key.vk_name().parse::<VirtualKey>().unwrap(), *key);
+            assert_eq!(VirtualKey::from_code(key.code()), Some(*key));
+        }
+    }
+
+    #[test]
+    fn chord_errors() {
+        let error = "Ctrl+Shfit+F9".parse::<Chord>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown key `Shfit`, did you mean `Shift`?");
+        assert_eq!(
+            "Ctrl++".parse::<Chord>().unwrap_err(),
+            KeyParseError::Missing("Ctrl++".into())
+        );
+        assert!(!"Foo".parse::<Chord>().unwrap_err().to_string().contains("did you mean"));
+        assert!(serde_json::from_str::<Chord>(r#"["VK_CONTROL", "VK_NOPE"]"#).is_err());
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("Shift", "shift"), 0);
+        assert_eq!(edit_distance("Shift", "Shfit"), 1);
+        assert_eq!(edit_distance("F9", "F10"), 2);
+        assert_eq!(edit_distance("", "Alt"), 3);
+    }
+}
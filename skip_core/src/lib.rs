@@ -0,0 +1,27 @@
+//! Platform independent logic of SkipTheRunback.
+//!
+//! Everything which touches the game process (memory, hooks, input, module lookups) goes through the [platform::Platform]
+//! and [keys::KeyInput] traits, so that each host (the Windows DLL, or any other) only has to provide those.
+
+pub mod actions;
+pub mod app;
+pub mod chains;
+pub mod config;
+pub mod discovery;
+pub mod format;
+#[cfg(feature = "frida")]
+pub mod frida;
+pub mod keys;
+pub mod memory;
+pub mod migrations;
+pub mod paths;
+pub mod platform;
+pub mod plugins;
+pub mod runner;
+pub mod scanner;
+pub mod schema;
+pub mod signature;
+#[cfg(feature = "frida")]
+mod utils;
+pub mod watcher;
+pub mod waypoints;
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::memory::{checked_range, MemoryAccess, MemoryError, MemoryResult};
+use crate::scanner::{MemoryRegion, MemorySource, Module};
+
+/// Fake address space consisting of explicitly mapped regions.
+///
+/// Accesses follow the same rules as the process memory of a host, an access has to fall entirely within a single mapped
+/// region, and writes require the region to be writable.
+#[derive(Debug, Default)]
+pub struct MockMemory {
+    regions: Mutex<BTreeMap<usize, MockRegion>>,
+    modules: Vec<Module>,
+}
+
+#[derive(Debug)]
+struct MockRegion {
+    data: Vec<u8>,
+    writable: bool,
+}
+
+impl MockMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a zeroed region of `size` bytes at `base`.
+    pub fn map(&self, base: usize, size: usize, writable: bool) {
+        self.map_bytes(base, vec![0; size], writable)
+    }
+
+    /// Map a region at `base` with the given contents.
+    pub fn map_bytes(&self, base: usize, data: Vec<u8>, writable: bool) {
+        self.regions.lock().unwrap().insert(base, MockRegion { data, writable });
+    }
+
+    /// Remove the region starting at `base`, simulating the game freeing it.
+    pub fn unmap(&self, base: usize) {
+        self.regions.lock().unwrap().remove(&base);
+    }
+
+    /// Register a module, its memory still has to be mapped separately.
+    pub fn add_module(&mut self, name: impl Into<String>, base: usize, size: usize) {
+        self.modules.push(Module {
+            name: name.into(),
+            base,
+            size,
+        });
+    }
+
+    fn with_region<T>(
+        &self,
+        address: usize,
+        len: usize,
+        f: impl FnOnce(&mut MockRegion, usize) -> MemoryResult<T>,
+    ) -> MemoryResult<T> {
+        let end = checked_range(address, len)?;
+        let mut regions = self.regions.lock().unwrap();
+        let (base, region) = regions
+            .range_mut(..=address)
+            .next_back()
+            .ok_or(MemoryError::Unmapped { address })?;
+
+        if end > base + region.data.len() {
+            return Err(MemoryError::Unmapped {
+                address: (base + region.data.len()).max(address),
+            });
+        }
+
+        f(region, address - base)
+    }
+}
+
+impl MemoryAccess for MockMemory {
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> MemoryResult<()> {
+        self.with_region(address, buf.len(), |region, offset| {
+            buf.copy_from_slice(&region.data[offset..offset + buf.len()]);
+            Ok(())
+        })
+    }
+
+    fn write_bytes(&self, address: usize, data: &[u8]) -> MemoryResult<()> {
+        self.with_region(address, data.len(), |region, offset| {
+            if !region.writable {
+                return Err(MemoryError::NotWritable { address });
+            }
+
+            region.data[offset..offset + data.len()].copy_from_slice(data);
+            Ok(())
+        })
+    }
+}
+
+impl MemorySource for MockMemory {
+    fn regions(&self) -> Vec<MemoryRegion> {
+        self.regions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(base, region)| MemoryRegion {
+                base: *base,
+                size: region.data.len(),
+            })
+            .collect()
+    }
+
+    fn read(&self, address: usize, buf: &mut [u8]) -> eyre::Result<()> {
+        Ok(self.read_bytes(address, buf)?)
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        self.modules.clone()
+    }
+}
@@ -0,0 +1,73 @@
+//! Validated access to (game) memory.
+//!
+//! Plugins never dereference raw pointers themselves, but go through a [MemoryAccess] implementation. In the game this
+//! is provided by the host, which checks page protections before touching anything, while `MockMemory` (behind the
+//! `test-utils` feature) provides a fake address space for exercising plugin logic on any platform.
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use mock::MockMemory;
+
+#[cfg(any(test, feature = "test-utils"))]
+mod mock;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MemoryError {
+    #[error("Attempted to access a null pointer")]
+    NullPointer,
+    #[error("Address range {address:#X} + {len:#X} overflows the address space")]
+    Overflow { address: usize, len: usize },
+    #[error("Address {address:#X} lies outside of the process' address space")]
+    OutOfProcess { address: usize },
+    #[error("Address {address:#X} is not mapped")]
+    Unmapped { address: usize },
+    #[error("Address {address:#X} is not readable")]
+    NotReadable { address: usize },
+    #[error("Address {address:#X} is not writable")]
+    NotWritable { address: usize },
+    #[error("Failed to access {len:#X} bytes at {address:#X}: {reason}")]
+    AccessFailed { address: usize, len: usize, reason: String },
+}
+
+pub type MemoryResult<T> = Result<T, MemoryError>;
+
+pub trait MemoryAccess: Send + Sync {
+    /// Read `buf.len()` bytes starting at `address`.
+    ///
+    /// # Errors
+    ///
+    /// If any part of the range isn't readable, in which case nothing is read.
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> MemoryResult<()>;
+
+    /// Write all of `data` starting at `address`.
+    ///
+    /// # Errors
+    ///
+    /// If any part of the range isn't writable, in which case nothing is written.
+    fn write_bytes(&self, address: usize, data: &[u8]) -> MemoryResult<()>;
+
+    fn read_f32(&self, address: usize) -> MemoryResult<f32> {
+        let mut buf = [0; 4];
+        self.read_bytes(address, &mut buf)?;
+        Ok(f32::from_ne_bytes(buf))
+    }
+
+    fn write_f32(&self, address: usize, value: f32) -> MemoryResult<()> {
+        self.write_bytes(address, &value.to_ne_bytes())
+    }
+
+    /// Read a native pointer at `address`.
+    fn read_pointer(&self, address: usize) -> MemoryResult<usize> {
+        let mut buf = [0; std::mem::size_of::<usize>()];
+        self.read_bytes(address, &mut buf)?;
+        Ok(usize::from_ne_bytes(buf))
+    }
+}
+
+/// Check the basic sanity of the range `address..address + len`, returning the exclusive end.
+pub fn checked_range(address: usize, len: usize) -> MemoryResult<usize> {
+    if address == 0 {
+        return Err(MemoryError::NullPointer);
+    }
+
+    address.checked_add(len).ok_or(MemoryError::Overflow { address, len })
+}
@@ -0,0 +1,259 @@
+//! Upgrading files written by older versions to the current format.
+//!
+//! Every versioned file has a `version` field, files from before versioning was introduced are version `0`. Files are
+//! upgraded step by step on the raw JSON, so a migration only has to know about the format directly before it.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::format::Format;
+
+/// Upgrade a document from the version equal to its index in [Versioned::MIGRATIONS] to the next version.
+pub type Migration = fn(&mut Value) -> eyre::Result<()>;
+
+pub trait Versioned {
+    /// Name of the document type, used in log messages.
+    const NAME: &'static str;
+    /// All migrations ever made, the `n`-th migration upgrades version `n` to `n + 1`.
+    const MIGRATIONS: &'static [Migration];
+    /// The version of documents written by this build.
+    const VERSION: u32 = Self::MIGRATIONS.len() as u32;
+}
+
+/// The current version of `T`, as the default for `version` fields.
+pub fn current_version<T: Versioned>() -> u32 {
+    T::VERSION
+}
+
+/// The version of a document, documents from before versioning are version `0`.
+pub fn document_version(document: &Value) -> eyre::Result<u32> {
+    match document.get("version") {
+        None => Ok(0),
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| eyre::eyre!("Invalid version `{version}`, expected a positive number")),
+    }
+}
+
+/// Upgrade `document` to [Versioned::VERSION], returns the original version if anything was changed.
+///
+/// # Errors
+///
+/// If the document is from a newer version, or a migration failed.
+///
+/// # Example
+/// ```rust
+/// # use skip_core::migrations::{migrate, Versioned};
+/// # use skip_core::plugins::generic::GenericConfig;
+/// let mut document = serde_json::json!({ "identifiers": {} });
+/// assert_eq!(migrate::<GenericConfig>(&mut document).unwrap(), Some(0));
+/// assert_eq!(document["version"], GenericConfig::VERSION);
+/// ```
+pub fn migrate<T: Versioned>(document: &mut Value) -> eyre::Result<Option<u32>> {
+    let original = document_version(document)?;
+    eyre::ensure!(
+        original <= T::VERSION,
+        "{} version {original} was written by a newer version of SkipTheRunback, which supports up to version {}",
+        T::NAME,
+        T::VERSION
+    );
+    if original == T::VERSION {
+        return Ok(None);
+    }
+
+    for (version, migration) in T::MIGRATIONS.iter().enumerate().skip(original as usize) {
+        migration(document).map_err(|e| eyre::eyre!("Failed to upgrade {} from version {version}: {e}", T::NAME))?;
+    }
+    if let Value::Object(fields) = document {
+        fields.insert("version".into(), T::VERSION.into());
+    }
+
+    Ok(Some(original))
+}
+
+/// Upgrade the file at `path` (with the contents `data`) if it's from an older version.
+///
+/// The file is read in the [Format] matching its extension. JSON files are backed up and then overwritten, files in a
+/// format with comments are only upgraded in memory, as their comments wouldn't survive. Returns the (upgraded)
+/// contents, data which can't be parsed is returned as is, to let the actual deserialization report the error.
+pub fn migrate_file<T: Versioned>(path: &Path, data: Vec<u8>) -> eyre::Result<Vec<u8>> {
+    let format = Format::from_path(path).unwrap_or(Format::Json);
+    let Ok(mut document) = format.parse_value(&data) else {
+        return Ok(data);
+    };
+    let Some(original) = migrate::<T>(&mut document)? else {
+        return Ok(data);
+    };
+
+    let upgraded = format.to_string(&document)?.into_bytes();
+    if format.has_comments() {
+        log::warn!(
+            "{} {path:?} is from version {original}, it was upgraded to version {} in memory to keep its comments. \
+            Update the file and set its `version` to {} to get rid of this warning",
+            T::NAME,
+            T::VERSION,
+            T::VERSION
+        );
+        return Ok(upgraded);
+    }
+
+    let backup = backup_path(path, original);
+    std::fs::write(&backup, &data)?;
+    std::fs::write(path, &upgraded)?;
+
+    log::info!(
+        "Upgraded {} {path:?} from version {original} to {}, the original was saved to {backup:?}",
+        T::NAME,
+        T::VERSION
+    );
+    Ok(upgraded)
+}
+
+/// Read the file at `path`, upgrading it first if needed, see [migrate_file].
+pub fn read_migrated<T: Versioned>(path: &Path) -> eyre::Result<Vec<u8>> {
+    let data = std::fs::read(path)?;
+    migrate_file::<T>(path, data)
+}
+
+/// `skip_rs_config.json` -> `skip_rs_config.json.v0.bak`, a different extension ensures backups of plugins aren't loaded.
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}.bak"));
+    path.with_file_name(name)
+}
+
+/// The initial migration for documents from before versioning, which only adds the `version` field.
+pub(crate) fn add_version(_document: &mut Value) -> eyre::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SkipConfig;
+    use crate::plugins::generic::GenericConfig;
+    use crate::waypoints::WaypointSave;
+
+    /// A plugin config from before versioning, do not update it.
+    const PLUGIN_V0: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/plugin_v0.json"));
+    /// The default config as written before versioning, do not update it.
+    const CONFIG_V0: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/config_v0.json"));
+    /// A waypoint save from before versioning, do not update it.
+    const WAYPOINTS_V0: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/waypoints_v0.json"));
+
+    fn plugin_v1() -> Value {
+        let mut expected: Value = serde_json::from_str(PLUGIN_V0).unwrap();
+        expected["version"] = 1.into();
+        expected
+    }
+
+    #[test]
+    fn migrate_v0() {
+        let mut document = serde_json::from_str(PLUGIN_V0).unwrap();
+        assert_eq!(migrate::<GenericConfig>(&mut document).unwrap(), Some(0));
+        assert_eq!(document, plugin_v1());
+
+        let config: GenericConfig = serde_json::from_value(document).unwrap();
+        assert_eq!(config.version, GenericConfig::VERSION);
+        assert_eq!(config.pointer_offsets.x, 128);
+    }
+
+    #[test]
+    fn migrate_current_is_unchanged() {
+        let mut document = plugin_v1();
+        assert_eq!(migrate::<GenericConfig>(&mut document).unwrap(), None);
+        assert_eq!(document, plugin_v1());
+    }
+
+    #[test]
+    fn migrate_rejects_newer_version() {
+        let mut document = serde_json::json!({ "version": GenericConfig::VERSION + 1 });
+        let error = migrate::<GenericConfig>(&mut document).unwrap_err().to_string();
+        assert!(error.contains("newer version"), "{error}");
+
+        let mut document = serde_json::json!({ "version": "1" });
+        assert!(migrate::<GenericConfig>(&mut document).is_err());
+    }
+
+    #[test]
+    fn migrate_file_writes_backup() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("plugin.json");
+        std::fs::write(&path, PLUGIN_V0).unwrap();
+
+        let upgraded = read_migrated::<GenericConfig>(&path).unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&upgraded).unwrap(), plugin_v1());
+        assert_eq!(std::fs::read(&path).unwrap(), upgraded);
+        assert_eq!(
+            std::fs::read_to_string(directory.path().join("plugin.json.v0.bak")).unwrap(),
+            PLUGIN_V0
+        );
+
+        // Upgraded files are left alone from then on.
+        assert_eq!(read_migrated::<GenericConfig>(&path).unwrap(), upgraded);
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 2);
+    }
+
+    /// Upgrade the unversioned `data` as the file `name`, checking that the original was backed up.
+    fn migrate_frozen<T: Versioned + serde::de::DeserializeOwned>(name: &str, data: &str) -> T {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(name);
+        std::fs::write(&path, data).unwrap();
+
+        let upgraded = read_migrated::<T>(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), upgraded);
+        assert_eq!(
+            std::fs::read_to_string(directory.path().join(format!("{name}.v0.bak"))).unwrap(),
+            data
+        );
+
+        serde_json::from_slice(&upgraded).unwrap()
+    }
+
+    #[test]
+    fn migrate_config_v0() {
+        let config: SkipConfig = migrate_frozen(&format!("{}.json", crate::config::CONFIG_FILE_STEM), CONFIG_V0);
+        let defaults = SkipConfig::default();
+
+        assert_eq!(config.version, SkipConfig::VERSION);
+        assert_eq!(config.reload_config_keys, defaults.reload_config_keys);
+        assert_eq!(config.keybinds, defaults.keybinds);
+    }
+
+    #[test]
+    fn migrate_waypoints_v0() {
+        let save: WaypointSave = migrate_frozen(crate::app::WAYPOINTS_FILE_NAME, WAYPOINTS_V0);
+
+        assert_eq!(save.version, WaypointSave::VERSION);
+        assert_eq!(save.most_recent.unwrap().z, 480.0);
+        assert!(save.slots.is_empty());
+    }
+
+    #[test]
+    fn migrate_file_keeps_comments() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("plugin.json5");
+        let data = format!("// Comment\n{PLUGIN_V0}");
+        std::fs::write(&path, &data).unwrap();
+
+        let upgraded = read_migrated::<GenericConfig>(&path).unwrap();
+        assert_eq!(Format::Json5.parse_value(&upgraded).unwrap(), plugin_v1());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), data);
+        assert_eq!(
+            std::fs::read_dir(directory.path()).unwrap().count(),
+            1,
+            "No backup is written"
+        );
+    }
+
+    #[test]
+    fn migrate_file_passes_on_invalid_data() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("plugin.json");
+
+        assert_eq!(migrate_file::<GenericConfig>(&path, b"{".to_vec()).unwrap(), b"{");
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,203 @@
+//! Where the config, and the data written while playing (waypoints, pointer chains, ...), are stored.
+
+use std::path::{Path, PathBuf};
+
+use eyre::{Context, ContextCompat};
+
+use crate::config::CONFIG_FILE_STEM;
+use crate::plugins::generic::{PLUGIN_DIRECTORY_NAME, SKIP_PLUGIN_FILENAME};
+
+/// Environment variable which, if set, overrides the directory containing the config.
+pub static CONFIG_DIRECTORY_VAR: &str = "SKIP_RUNBACK_DIR";
+/// Name of the directory created in the per-user config directory, see [user_directory].
+static APP_DIRECTORY_NAME: &str = "SkipTheRunback";
+
+/// The directory containing the config, for a host installed in `install_directory`.
+///
+/// In order of priority:
+/// 1. The [CONFIG_DIRECTORY_VAR] environment variable, in which other environment variables are expanded.
+/// 2. `install_directory`, if it already contains a config, plugin configs or the legacy plugin config. This keeps
+///    existing installs working.
+/// 3. The per-user directory, see [user_directory]. Unlike the install directory this is always writable, and not
+///    shared between the users of a machine.
+/// 4. `install_directory`, if the per-user directory can't be determined.
+///
+/// The chosen directory is logged, along with the files in `install_directory` which are ignored because of it.
+pub fn config_directory(install_directory: &Path) -> eyre::Result<PathBuf> {
+    let legacy = legacy_files(install_directory);
+
+    if let Ok(directory) = std::env::var(CONFIG_DIRECTORY_VAR) {
+        let directory = expand_env(&directory)?;
+        log::info!("Using the config directory {directory:?} set by `{CONFIG_DIRECTORY_VAR}`");
+        if !legacy.is_empty() && directory != install_directory {
+            log::warn!("Ignoring {legacy:?} in the install directory, move them to {directory:?} to use them");
+        }
+        return Ok(directory);
+    }
+    if !legacy.is_empty() {
+        log::info!(
+            "Using the install directory {install_directory:?} as the config directory, as it contains {legacy:?}"
+        );
+        return Ok(install_directory.to_path_buf());
+    }
+
+    match user_directory() {
+        Some(directory) => {
+            log::info!("Using the per-user config directory {directory:?}");
+            Ok(directory)
+        }
+        None => {
+            log::warn!(
+                "Couldn't determine the per-user config directory, using the install directory {install_directory:?}"
+            );
+            Ok(install_directory.to_path_buf())
+        }
+    }
+}
+
+/// The config, plugin directory and legacy plugin config in `directory`, which make it a config directory.
+fn legacy_files(directory: &Path) -> Vec<PathBuf> {
+    let config = crate::format::find_file(directory, CONFIG_FILE_STEM).map(|(path, _)| path);
+    let plugins = Some(directory.join(PLUGIN_DIRECTORY_NAME)).filter(|path| path.is_dir());
+    let legacy_plugin = Some(directory.join(SKIP_PLUGIN_FILENAME)).filter(|path| path.is_file());
+
+    [config, plugins, legacy_plugin].into_iter().flatten().collect()
+}
+
+/// The per-user directory for SkipTheRunback, being `%APPDATA%\SkipTheRunback` on Windows and
+/// `$XDG_CONFIG_HOME/SkipTheRunback` (or `$HOME/.config/SkipTheRunback`) elsewhere.
+pub fn user_directory() -> Option<PathBuf> {
+    let env_path = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = if cfg!(windows) {
+        env_path("APPDATA")?
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| env_path("HOME").map(|home| home.join(".config")))?
+    };
+
+    Some(base.join(APP_DIRECTORY_NAME))
+}
+
+/// Expand the environment variables in `path`, written as `%NAME%`, `$NAME` or `${NAME}`.
+///
+/// A `%` or `$` which isn't followed by a name is kept as is, e.g. in `50% of %HOME%` only `%HOME%` is expanded. `%%`
+/// is an escaped `%`.
+///
+/// # Errors
+///
+/// If a variable isn't set.
+///
+/// # Example
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use skip_core::paths::expand_env;
+/// std::env::set_var("SKIP_EXAMPLE_HOME", "/home/skip");
+/// assert_eq!(expand_env("%SKIP_EXAMPLE_HOME%/saves").unwrap(), PathBuf::from("/home/skip/saves"));
+/// ```
+pub fn expand_env(path: &str) -> eyre::Result<PathBuf> {
+    let mut expanded = String::new();
+    let mut rest = path;
+
+    while let Some(start) = rest.find(['%', '$']) {
+        expanded.push_str(&rest[..start]);
+        let sigil = &rest[start..start + 1];
+        let after = &rest[start + 1..];
+
+        let (name, remaining) = if sigil == "%" {
+            // Windows allows parentheses in names, like `%ProgramFiles(x86)%`.
+            let is_name = |name: &str| {
+                name.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '(' | ')'))
+            };
+            match after.find('%') {
+                Some(end) if is_name(&after[..end]) => (&after[..end], &after[end + 1..]),
+                // Not a variable, the closing `%` might start one though.
+                _ => ("", after),
+            }
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .with_context(|| format!("Missing closing `}}` in `{path}`"))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+
+        if name.is_empty() {
+            expanded.push_str(sigil);
+        } else {
+            let value = std::env::var(name)
+                .with_context(|| format!("The environment variable `{name}` used in `{path}` isn't set"))?;
+            expanded.push_str(&value);
+        }
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+
+    Ok(PathBuf::from(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expanded(path: &str) -> String {
+        std::env::set_var("SKIP_TEST_HOME", "/home/skip");
+        expand_env(path).unwrap().to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn expand_variables() {
+        assert_eq!(expanded("%SKIP_TEST_HOME%/saves"), "/home/skip/saves");
+        assert_eq!(expanded("$SKIP_TEST_HOME/saves"), "/home/skip/saves");
+        assert_eq!(expanded("${SKIP_TEST_HOME}_2"), "/home/skip_2");
+        assert_eq!(expanded("%SKIP_TEST_HOME%%SKIP_TEST_HOME%"), "/home/skip/home/skip");
+    }
+
+    #[test]
+    fn expand_literal_signs() {
+        assert_eq!(expanded("100%% $ saves"), "100% $ saves");
+        assert_eq!(expanded("100% saves"), "100% saves");
+        assert_eq!(expanded("50% of %SKIP_TEST_HOME%"), "50% of /home/skip");
+        assert_eq!(expanded("50% of $SKIP_TEST_HOME"), "50% of /home/skip");
+        assert_eq!(expanded("%SKIP_TEST_HOME%/100%"), "/home/skip/100%");
+    }
+
+    #[test]
+    fn expand_errors() {
+        assert!(expand_env("%SKIP_TEST_UNSET%").is_err());
+        assert!(expand_env("${SKIP_TEST_HOME").is_err());
+    }
+
+    #[test]
+    fn config_directory_priority() {
+        let install = tempfile::tempdir().unwrap();
+        let user = user_directory();
+        let fallback = || user.clone().unwrap_or_else(|| install.path().to_path_buf());
+        assert_eq!(config_directory(install.path()).unwrap(), fallback());
+
+        // Any of the files an existing install might have keeps it in the install directory.
+        std::fs::write(install.path().join(SKIP_PLUGIN_FILENAME), "{}").unwrap();
+        assert_eq!(config_directory(install.path()).unwrap(), install.path());
+        std::fs::remove_file(install.path().join(SKIP_PLUGIN_FILENAME)).unwrap();
+
+        std::fs::create_dir(install.path().join(PLUGIN_DIRECTORY_NAME)).unwrap();
+        assert_eq!(config_directory(install.path()).unwrap(), install.path());
+        std::fs::remove_dir(install.path().join(PLUGIN_DIRECTORY_NAME)).unwrap();
+
+        std::fs::write(install.path().join("skip_rs_config.json"), "{}").unwrap();
+        assert_eq!(config_directory(install.path()).unwrap(), install.path());
+
+        std::env::set_var(CONFIG_DIRECTORY_VAR, "/elsewhere");
+        let overridden = config_directory(install.path());
+        std::env::remove_var(CONFIG_DIRECTORY_VAR);
+        assert_eq!(overridden.unwrap(), Path::new("/elsewhere"));
+    }
+}
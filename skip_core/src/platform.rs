@@ -0,0 +1,170 @@
+//! The interface between the platform independent logic and the process it runs in.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use eyre::ContextCompat;
+
+use crate::memory::MemoryAccess;
+use crate::plugins::context::RegisterContext;
+use crate::scanner::{MemorySource, Module, ScanOptions};
+use crate::signature::Signature;
+
+/// Everything a plugin (or the app) needs from the process it's running in.
+pub trait Platform: Send + Sync {
+    /// The memory backend used for reading/writing game memory.
+    fn memory(&self) -> Arc<dyn MemoryAccess>;
+
+    /// The main module (executable) of the process.
+    fn main_module(&self) -> Option<Module>;
+
+    /// Find a loaded module by its file name, e.g. `GameAssembly.dll`.
+    fn module(&self, name: &str) -> Option<Module>;
+
+    /// Find the address of the first match of `signature` in the given module, or the main module if [None].
+    fn find_signature(&self, module: Option<&str>, signature: &str) -> eyre::Result<usize> {
+        let module = match module {
+            Some(name) => self
+                .module(name)
+                .with_context(|| format!("Module `{name}` is not loaded"))?,
+            None => self.main_module().context("Failed to get the main module")?,
+        };
+        let signature = Signature::parse(signature)?;
+        let mut data = vec![0; module.size];
+        self.memory().read_bytes(module.base, &mut data)?;
+
+        let offset = signature
+            .find(&data)
+            .with_context(|| format!("Signature not found in `{}`", module.name))?;
+
+        Ok(module.base + offset)
+    }
+
+    /// Attach `listener` to the instruction at `address`, it will be called every time the instruction is executed.
+    ///
+    /// The listener stays attached until the returned [Hook] is dropped.
+    fn attach(&self, address: usize, listener: Box<dyn HitListener>) -> eyre::Result<Hook>;
+
+    /// A view of the process' memory for scanning.
+    fn memory_source(&self, options: ScanOptions) -> Box<dyn MemorySource>;
+}
+
+/// Called whenever a hooked instruction is executed.
+pub trait HitListener: Send {
+    fn on_hit(&mut self, ctx: &dyn RegisterContext);
+}
+
+/// Guard of an attached [HitListener], detaches the listener when dropped.
+pub struct Hook(#[allow(dead_code)] Box<dyn Any>);
+
+impl Hook {
+    /// Wrap a platform specific guard, whose [Drop] implementation has to detach the listener.
+    pub fn new(guard: impl Any) -> Self {
+        Self(Box::new(guard))
+    }
+}
+
+impl std::fmt::Debug for Hook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hook").finish_non_exhaustive()
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use mock::MockPlatform;
+
+#[cfg(any(test, feature = "test-utils"))]
+mod mock {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, Weak};
+
+    use super::{HitListener, Hook, Platform};
+    use crate::memory::{MemoryAccess, MockMemory};
+    use crate::plugins::context::RegisterContext;
+    use crate::scanner::{MemorySource, Module, ScanOptions};
+
+    type MockHooks = Mutex<HashMap<usize, (usize, Box<dyn HitListener>)>>;
+
+    /// [Platform] backed by a [MockMemory], where hooks are only ever hit through [MockPlatform::hit].
+    pub struct MockPlatform {
+        memory: Arc<MockMemory>,
+        main_module: Option<Module>,
+        hooks: Arc<MockHooks>,
+        next_hook: Mutex<usize>,
+    }
+
+    impl MockPlatform {
+        /// Create a platform on top of `memory`, where the module called `main_module` (if any) is the executable.
+        pub fn new(memory: Arc<MockMemory>, main_module: Option<&str>) -> Self {
+            let main_module = main_module.and_then(|name| memory.modules().into_iter().find(|m| m.name == name));
+
+            Self {
+                memory,
+                main_module,
+                hooks: Default::default(),
+                next_hook: Mutex::new(0),
+            }
+        }
+
+        /// Simulate the execution of the instruction at `address` with the given register state.
+        ///
+        /// Returns the amount of listeners which were hit.
+        pub fn hit(&self, address: usize, ctx: &dyn RegisterContext) -> usize {
+            let mut hooks = self.hooks.lock().unwrap();
+            hooks
+                .values_mut()
+                .filter(|(hook_address, _)| *hook_address == address)
+                .map(|(_, listener)| listener.on_hit(ctx))
+                .count()
+        }
+
+        /// The amount of currently attached listeners.
+        pub fn attached(&self) -> usize {
+            self.hooks.lock().unwrap().len()
+        }
+    }
+
+    impl Platform for MockPlatform {
+        fn memory(&self) -> Arc<dyn MemoryAccess> {
+            self.memory.clone()
+        }
+
+        fn main_module(&self) -> Option<Module> {
+            self.main_module.clone()
+        }
+
+        fn module(&self, name: &str) -> Option<Module> {
+            self.memory.modules().into_iter().find(|m| m.name == name)
+        }
+
+        fn attach(&self, address: usize, listener: Box<dyn HitListener>) -> eyre::Result<Hook> {
+            let mut next = self.next_hook.lock().unwrap();
+            let id = *next;
+            *next += 1;
+
+            self.hooks.lock().unwrap().insert(id, (address, listener));
+
+            Ok(Hook::new(MockHook {
+                id,
+                hooks: Arc::downgrade(&self.hooks),
+            }))
+        }
+
+        fn memory_source(&self, _options: ScanOptions) -> Box<dyn MemorySource> {
+            Box::new(self.memory.clone())
+        }
+    }
+
+    struct MockHook {
+        id: usize,
+        hooks: Weak<MockHooks>,
+    }
+
+    impl Drop for MockHook {
+        fn drop(&mut self) {
+            if let Some(hooks) = self.hooks.upgrade() {
+                hooks.lock().unwrap().remove(&self.id);
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Abstraction over the CPU context available when an intercept is hit.
+//!
+//! Listeners only inspect registers through [RegisterContext], which allows their logic to be driven by scripted hits
+//! through `MockRegisters` (behind the `test-utils` feature) instead of actual hooks.
+
+use crate::plugins::generic::Register;
+
+pub trait RegisterContext {
+    /// Retrieve the current value of `register`.
+    fn register(&self, register: Register) -> u64;
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+pub use mock::MockRegisters;
+
+#[cfg(any(test, feature = "test-utils"))]
+mod mock {
+    use std::collections::HashMap;
+
+    use super::RegisterContext;
+    use crate::plugins::generic::Register;
+
+    /// Register state for a scripted intercept hit, registers which weren't set read as `0`.
+    #[derive(Debug, Clone, Default)]
+    pub struct MockRegisters {
+        registers: HashMap<Register, u64>,
+    }
+
+    impl MockRegisters {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn with(mut self, register: Register, value: u64) -> Self {
+            self.set(register, value);
+            self
+        }
+
+        pub fn set(&mut self, register: Register, value: u64) {
+            self.registers.insert(register, value);
+        }
+    }
+
+    impl RegisterContext for MockRegisters {
+        fn register(&self, register: Register) -> u64 {
+            self.registers.get(&register).copied().unwrap_or_default()
+        }
+    }
+}
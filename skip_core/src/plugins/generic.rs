@@ -0,0 +1,1133 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::format::Format;
+use crate::memory::MemoryAccess;
+use crate::migrations;
+use crate::platform::{HitListener, Hook, Platform};
+use crate::plugins::context::RegisterContext;
+pub use crate::plugins::generic::config::*;
+pub use crate::plugins::{CapturedPointer, CoordinatePtr, PlayerCoordinates, PluginIdentifiers, SkipPlugin};
+
+pub static SKIP_PLUGIN_FILENAME: &str = "skip_runback_plugin.json";
+/// Directory (relative to the config directory) from which any amount of plugin configs are loaded, in any [Format].
+pub static PLUGIN_DIRECTORY_NAME: &str = "plugins";
+
+/// The intercept config shared between a plugin and its attached listener, so that it can be updated in place.
+pub type SharedInterceptConfig = Arc<Mutex<InterceptConfig>>;
+
+pub struct ConfigBasedPlugin {
+    position_ptr: CoordinatePtr,
+    listener: Option<(Hook, SharedInterceptConfig)>,
+    config_path: Option<PathBuf>,
+    config: GenericConfig,
+    memory: Arc<dyn MemoryAccess>,
+    platform: Arc<dyn Platform>,
+    /// Validated chains which are used while the intercept hasn't captured a pointer yet.
+    fallback_chains: Vec<PointerChain>,
+}
+
+impl ConfigBasedPlugin {
+    /// Load all plugin configs in `base_path`, see [ConfigBasedPlugin::load_configs].
+    ///
+    /// Configs which fail to load are reported and skipped, so one broken file doesn't disable all the others.
+    pub fn load_all(base_path: &Path, platform: &Arc<dyn Platform>) -> eyre::Result<Vec<ConfigBasedPlugin>> {
+        let output = Self::load_configs(base_path)?
+            .into_iter()
+            .filter_map(|(file, config)| match config {
+                Ok(config) => {
+                    log::debug!("Loaded plugin `{}` from {file:?}", config.identifiers.plugin_name);
+                    let mut plugin = Self::from_config(config, platform.clone());
+                    plugin.config_path = Some(file);
+                    Some(plugin)
+                }
+                Err(e) => {
+                    log::warn!("Failed to load plugin config {file:?}, skipping it: {e}");
+                    None
+                }
+            })
+            .collect();
+
+        Ok(output)
+    }
+
+    /// Read all plugin configs in `base_path`, being the legacy [SKIP_PLUGIN_FILENAME] and every `*.json`, `*.json5` or
+    /// `*.toml` file in the [PLUGIN_DIRECTORY_NAME] directory.
+    ///
+    /// Configs from older versions are upgraded on disk, see [migrations::read_migrated].
+    pub fn load_configs(base_path: &Path) -> eyre::Result<Vec<(PathBuf, eyre::Result<GenericConfig>)>> {
+        Self::configs_with(base_path, Self::load_config)
+    }
+
+    /// Like [ConfigBasedPlugin::load_configs], but the files are left untouched and only upgraded in memory.
+    pub fn read_configs(base_path: &Path) -> eyre::Result<Vec<(PathBuf, eyre::Result<GenericConfig>)>> {
+        Self::configs_with(base_path, |file| {
+            let format = Format::from_path(file).unwrap_or(Format::Json);
+            GenericConfig::parse(format, &std::fs::read(file)?)
+        })
+    }
+
+    fn configs_with(
+        base_path: &Path,
+        load: impl Fn(&Path) -> eyre::Result<GenericConfig>,
+    ) -> eyre::Result<Vec<(PathBuf, eyre::Result<GenericConfig>)>> {
+        let mut files = plugin_files(&base_path.join(PLUGIN_DIRECTORY_NAME))?;
+        let legacy = base_path.join(SKIP_PLUGIN_FILENAME);
+        if legacy.exists() {
+            files.insert(0, legacy);
+        }
+
+        Ok(files
+            .into_iter()
+            .map(|file| {
+                let config = load(&file);
+                (file, config)
+            })
+            .collect())
+    }
+
+    pub fn new(config_path: impl Into<PathBuf>, platform: Arc<dyn Platform>) -> eyre::Result<Self> {
+        let file = config_path.into();
+        let conf = Self::load_config(&file)?;
+
+        let mut plugin = Self::from_config(conf, platform);
+        plugin.config_path = Some(file);
+
+        Ok(plugin)
+    }
+
+    pub fn from_config(config: GenericConfig, platform: Arc<dyn Platform>) -> ConfigBasedPlugin {
+        Self {
+            position_ptr: CoordinatePtr::default(),
+            listener: None,
+            config_path: None,
+            config,
+            memory: platform.memory(),
+            platform,
+            fallback_chains: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &GenericConfig {
+        &self.config
+    }
+
+    fn load_config(cfg_path: &Path) -> eyre::Result<GenericConfig> {
+        let format = Format::from_path(cfg_path).unwrap_or(Format::Json);
+        let data = migrations::read_migrated::<GenericConfig>(cfg_path)?;
+        GenericConfig::parse(format, &data)
+    }
+
+    /// Create a listener which updates this plugin's player pointer when hit.
+    pub fn create_listener(&self, intercept: SharedInterceptConfig) -> GenericCoordinateIntercept {
+        GenericCoordinateIntercept {
+            position_ptr: self.position_ptr.clone(),
+            config: intercept,
+        }
+    }
+
+    /// Hook the code matching the intercept's signature in `module`, replacing the current hook (if any).
+    ///
+    /// The signature is searched before the current hook is detached, so a signature which can't be found leaves the
+    /// current hook in place.
+    fn start_intercept(&mut self, intercept: config::InterceptConfig, module: Option<&str>) -> eyre::Result<()> {
+        let position_fn_ptr = self.platform.find_signature(module, &intercept.intercept_signature)?;
+        log::info!("Found position modification ptr: {position_fn_ptr:#X}");
+
+        self.listener = None;
+        *self.position_ptr.lock().unwrap() = None;
+
+        let shared = SharedInterceptConfig::new(Mutex::new(intercept));
+        let listener = self.create_listener(shared.clone());
+        let hook = self.platform.attach(position_fn_ptr, Box::new(listener))?;
+        log::info!("Initiated interceptor at {position_fn_ptr:#X}");
+        self.listener = Some((hook, shared));
+
+        Ok(())
+    }
+
+    /// Switch to the position config of `new`, re-hooking if the intercepted code changed.
+    fn apply_position(&mut self, new: &GenericConfig) -> eyre::Result<()> {
+        let module = new.identifiers.expected_module.as_deref();
+
+        match (&self.config.position, &new.position, &self.listener) {
+            (
+                GenericPositionConfig::InterceptPtr(old),
+                GenericPositionConfig::InterceptPtr(intercept),
+                Some((_, shared)),
+            ) if old.intercept_signature == intercept.intercept_signature
+                && self.config.identifiers.expected_module.as_deref() == module =>
+            {
+                // The same code stays hooked, the listener picks the new register and filter up on its next hit.
+                *shared.lock().unwrap() = intercept.clone();
+                if old != intercept {
+                    *self.position_ptr.lock().unwrap() = None;
+                }
+            }
+            (_, GenericPositionConfig::InterceptPtr(intercept), _) => {
+                log::info!("The intercepted code changed, re-hooking");
+                self.start_intercept(intercept.clone(), module)?;
+            }
+            (_, GenericPositionConfig::AbsolutePtr(pointer), _) => {
+                // Chains are allowed to be unresolvable for now, see `player_ptr`.
+                if !matches!(pointer, PointerTypeConfig::Chain(_)) {
+                    pointer.get_non_null_ptr(&*self.platform)?;
+                }
+                if self.listener.take().is_some() {
+                    log::info!("Detached the interceptor, switching to the given pointer");
+                }
+                *self.position_ptr.lock().unwrap() = None;
+                self.start_given_ptr(pointer.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_given_ptr(&mut self, intercept: config::PointerTypeConfig) -> eyre::Result<()> {
+        // Chains are resolved on every access instead, see `player_ptr`.
+        if !matches!(intercept, PointerTypeConfig::Chain(_)) {
+            // The given pointer is never refreshed, but also never reported by `captured_pointer`, so it won't go stale.
+            *self.position_ptr.lock().unwrap() =
+                Some(CapturedPointer::new(intercept.get_non_null_ptr(&*self.platform)?.get()));
+        }
+
+        log::info!("Using given pointer pointing to `{intercept:#?}`");
+
+        Ok(())
+    }
+
+    /// Get the pointer to the player struct, to which the [OffsetsConfig] can be applied.
+    fn player_ptr(&self) -> Option<usize> {
+        if let GenericPositionConfig::AbsolutePtr(chain @ PointerTypeConfig::Chain(_)) = &self.config.position {
+            return chain.get_non_null_ptr(&*self.platform).ok().map(|ptr| ptr.get());
+        }
+
+        let captured = self.position_ptr.lock().unwrap().map(|ptr| ptr.address);
+
+        captured.or_else(|| {
+            self.fallback_chains
+                .iter()
+                .find_map(|chain| chain.resolve(&*self.platform).ok())
+        })
+    }
+}
+
+/// All files in `directory` with the extension of a [Format] sorted by name, or nothing if the directory doesn't exist.
+fn plugin_files(directory: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = std::fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && Format::from_path(path).is_some())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    Ok(files)
+}
+
+impl super::SkipPlugin for ConfigBasedPlugin {
+    fn identifiers(&self) -> PluginIdentifiers {
+        self.config.identifiers.clone()
+    }
+
+    fn start(&mut self) -> eyre::Result<()> {
+        match self.config.position.clone() {
+            GenericPositionConfig::InterceptPtr(cfg) => {
+                let module = self.config.identifiers.expected_module.clone();
+                self.start_intercept(cfg, module.as_deref())?
+            }
+            GenericPositionConfig::AbsolutePtr(cfg) => self.start_given_ptr(cfg)?,
+        }
+
+        Ok(())
+    }
+
+    fn get_current_coordinates(&mut self) -> eyre::Result<Option<PlayerCoordinates>> {
+        let Some(ptr) = self.player_ptr() else {
+            return Ok(None);
+        };
+        let offsets = &self.config.pointer_offsets;
+
+        Ok(Some(PlayerCoordinates {
+            x: self.memory.read_f32(ptr.wrapping_add_signed(offsets.x))?,
+            y: self.memory.read_f32(ptr.wrapping_add_signed(offsets.y))?,
+            z: self.memory.read_f32(ptr.wrapping_add_signed(offsets.z))?,
+        }))
+    }
+
+    fn set_current_coordinates(&mut self, coordinates: PlayerCoordinates) -> eyre::Result<()> {
+        let Some(ptr) = self.player_ptr() else {
+            eyre::bail!("Pointer not initialised")
+        };
+        let offsets = &self.config.pointer_offsets;
+
+        self.memory
+            .write_f32(ptr.wrapping_add_signed(offsets.x), coordinates.x)?;
+        self.memory
+            .write_f32(ptr.wrapping_add_signed(offsets.y), coordinates.y)?;
+        self.memory
+            .write_f32(ptr.wrapping_add_signed(offsets.z), coordinates.z)?;
+
+        Ok(())
+    }
+
+    fn captured_pointer(&self) -> Option<CapturedPointer> {
+        match self.config.position {
+            GenericPositionConfig::InterceptPtr(_) => *self.position_ptr.lock().unwrap(),
+            GenericPositionConfig::AbsolutePtr(_) => None,
+        }
+    }
+
+    fn set_fallback_chains(&mut self, chains: Vec<PointerChain>) {
+        self.fallback_chains = chains;
+    }
+
+    /// Reload the config from disk, re-hooking (or switching between [GenericPositionConfig] modes) if needed.
+    ///
+    /// On failure the previous config stays in use. If the new signature can't be found the previous hook stays
+    /// attached as well.
+    fn reload_config(&mut self) -> eyre::Result<()> {
+        let Some(path) = self.config_path.as_ref() else {
+            return Ok(());
+        };
+        let cfg = Self::load_config(path)?;
+        if self.config == cfg {
+            return Ok(());
+        }
+
+        self.apply_position(&cfg)?;
+        log::info!(
+            "Reloaded the config of plugin `{}`, changed settings:\n  {}",
+            self.config.identifiers.plugin_name,
+            crate::config::diff(&self.config, &cfg).join("\n  ")
+        );
+        self.config = cfg;
+
+        Ok(())
+    }
+}
+
+pub struct GenericCoordinateIntercept {
+    position_ptr: CoordinatePtr,
+    config: SharedInterceptConfig,
+}
+
+impl GenericCoordinateIntercept {
+    /// Process a single hit of the intercepted instruction.
+    pub fn handle_hit(&mut self, ctx: &dyn RegisterContext) {
+        let config = self.config.lock().unwrap();
+        if let Some(filter) = &config.filter {
+            if !filter.matches(ctx) {
+                return;
+            }
+        }
+
+        let base_ptr = config.register.to_value(ctx) as usize;
+        drop(config);
+
+        let mut lock = self.position_ptr.lock().unwrap();
+
+        match lock.as_mut() {
+            Some(captured) if captured.address == base_ptr => captured.last_hit = Instant::now(),
+            _ => {
+                let old = lock.map(|ptr| ptr.address).unwrap_or_default();
+                *lock = Some(CapturedPointer::new(base_ptr));
+                log::trace!("Updated player pointer from `{old:#X}` to {:#X}", base_ptr);
+            }
+        }
+    }
+}
+
+impl HitListener for GenericCoordinateIntercept {
+    fn on_hit(&mut self, ctx: &dyn RegisterContext) {
+        self.handle_hit(ctx);
+    }
+}
+
+mod config {
+    use std::fmt::{Display, Formatter};
+    use std::num::NonZeroUsize;
+
+    use eyre::ContextCompat;
+    use serde::Deserialize;
+
+    use crate::format::Format;
+    use crate::memory::MemoryAccess;
+    use crate::migrations;
+    use crate::migrations::{Migration, Versioned};
+    use crate::platform::Platform;
+    use crate::plugins::context::RegisterContext;
+    use crate::plugins::{hex, PluginIdentifiers};
+    use crate::signature::Signature;
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct GenericConfig {
+        /// The format version of this file, files from older versions are upgraded automatically.
+        #[serde(default = "crate::migrations::current_version::<GenericConfig>")]
+        pub version: u32,
+        /// The name of a built-in plugin this config inherits from, see [GenericConfig::extend].
+        ///
+        /// The rest of the config then only has to contain the fields which should be overridden.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub extends: Option<String>,
+        pub identifiers: PluginIdentifiers,
+        pub position: GenericPositionConfig,
+        /// The offset from the acquired pointer above for the x/y/z coordinates.
+        pub pointer_offsets: OffsetsConfig,
+    }
+
+    impl Default for GenericConfig {
+        fn default() -> Self {
+            Self {
+                version: Self::VERSION,
+                extends: None,
+                identifiers: PluginIdentifiers {
+                    plugin_name: "Generic Skip Sekiro Example".to_string(),
+                    expected_module: Some("sekiro.exe".to_string()),
+                    expected_exe_name: Some("sekiro.exe".to_string()),
+                },
+                position: GenericPositionConfig::InterceptPtr(InterceptConfig {
+                    intercept_signature: crate::plugins::sekiro::READ_FROM_COORDS_SIG.to_string(),
+                    register: Register::Rcx,
+                    filter: None,
+                }),
+                pointer_offsets: OffsetsConfig {
+                    x: 0x80,
+                    y: 0x80 + 4,
+                    z: 0x80 + 8,
+                },
+            }
+        }
+    }
+
+    impl Versioned for GenericConfig {
+        const NAME: &'static str = "plugin config";
+        const MIGRATIONS: &'static [Migration] = &[migrations::add_version];
+    }
+
+    impl GenericConfig {
+        /// Parse and [validate](GenericConfig::validate) a plugin config, upgrading it in memory if it's from an
+        /// older version and applying its [GenericConfig::extends].
+        pub fn parse(format: Format, data: &[u8]) -> eyre::Result<Self> {
+            #[derive(serde::Deserialize)]
+            struct Extends {
+                extends: Option<String>,
+            }
+
+            let mut document = format.parse_value(data)?;
+            let migrated = migrations::migrate::<Self>(&mut document)?.is_some();
+            let config: Self = match Extends::deserialize(&document)?.extends {
+                Some(name) => Self::extend(&name, document)?,
+                None if migrated => crate::format::from_value(document)?,
+                // Parsing the original data keeps the format's own error locations.
+                None => format.parse(data)?,
+            };
+
+            config.validate()?;
+            Ok(config)
+        }
+
+        /// Check the values which deserialize fine, but could never work.
+        pub fn validate(&self) -> eyre::Result<()> {
+            let identifiers = &self.identifiers;
+            eyre::ensure!(
+                !identifiers.plugin_name.trim().is_empty(),
+                "`identifiers.plugin_name` can't be empty"
+            );
+            eyre::ensure!(
+                identifiers.expected_module.is_some() || identifiers.expected_exe_name.is_some(),
+                "At least one of `identifiers.expected_module` and `identifiers.expected_exe_name` has to be set"
+            );
+
+            let relative = match &self.position {
+                GenericPositionConfig::InterceptPtr(intercept) => {
+                    Signature::parse(&intercept.intercept_signature)
+                        .map_err(|e| eyre::eyre!("Invalid `position.InterceptPtr.intercept_signature`: {e}"))?;
+                    None
+                }
+                GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Absolute(_)) => None,
+                GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Relative(pointer)) => Some(pointer),
+                GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Chain(chain)) => Some(&chain.base),
+            };
+            if let Some(pointer) = relative {
+                pointer
+                    .module_name()
+                    .and(pointer.offset())
+                    .map_err(|e| eyre::eyre!("Invalid pointer `{}`, expected `module+offset`: {e}", pointer.0))?;
+            }
+
+            Ok(())
+        }
+
+        /// Apply the (partial) `overrides` on top of the built-in plugin called `name`.
+        ///
+        /// Objects are merged recursively, so e.g. `{"pointer_offsets": {"x": 16}}` only replaces the x offset. Arrays
+        /// (e.g. the offsets of a [PointerChain]) and all other values replace the inherited value, as does a different
+        /// variant of an enum like [GenericPositionConfig].
+        pub fn extend(name: &str, overrides: serde_json::Value) -> eyre::Result<Self> {
+            let builtins = crate::plugins::builtin_configs();
+            let Some(base) = builtins.iter().find(|conf| conf.identifiers.plugin_name == name) else {
+                let names = builtins
+                    .iter()
+                    .map(|conf| format!("`{}`", conf.identifiers.plugin_name))
+                    .collect::<Vec<_>>();
+                eyre::bail!("Unknown built-in plugin `{name}`, expected one of {}", names.join(", "))
+            };
+
+            let mut merged = serde_json::to_value(base)?;
+            merge_json(&mut merged, overrides);
+
+            crate::format::from_value(merged)
+        }
+    }
+
+    /// Merge `overrides` into `base`, see [GenericConfig::extend].
+    pub(super) fn merge_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+        use serde_json::Value;
+
+        // Enums are serialized as an object with the variant as its only key, variants are `PascalCase` while fields
+        // are `snake_case`.
+        let variant = |object: &serde_json::Map<String, Value>| {
+            let mut keys = object.keys();
+            match (keys.next(), keys.next()) {
+                (Some(key), None) if key.starts_with(|c: char| c.is_ascii_uppercase()) => Some(key.clone()),
+                _ => None,
+            }
+        };
+        let is_other_variant = |base: &serde_json::Map<String, Value>, overrides: &serde_json::Map<String, Value>| matches!((variant(base), variant(overrides)), (Some(base), Some(overrides)) if base != overrides);
+
+        match (base, overrides) {
+            (Value::Object(base), Value::Object(overrides)) if !is_other_variant(base, &overrides) => {
+                for (key, value) in overrides {
+                    match base.get_mut(&key) {
+                        Some(existing) => merge_json(existing, value),
+                        None => {
+                            base.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (base, overrides) => *base = overrides,
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub enum GenericPositionConfig {
+        InterceptPtr(InterceptConfig),
+        AbsolutePtr(PointerTypeConfig),
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct InterceptConfig {
+        /// The signature of the code which will have the pointer to the player position passed during the execution
+        pub intercept_signature: String,
+        /// The register in which we'll find the player position pointer during execution of the code identifier by the above
+        /// signature.
+        pub register: Register,
+        /// Optional filter for which calls to the `intercept_signature` identified code should be ignored
+        /// (say other entities' positions are also altered by this code).
+        pub filter: Option<Filter>,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct OffsetsConfig {
+        #[serde(with = "hex")]
+        #[schemars(with = "hex::Hex<isize>")]
+        pub x: isize,
+        #[serde(with = "hex")]
+        #[schemars(with = "hex::Hex<isize>")]
+        pub y: isize,
+        #[serde(with = "hex")]
+        #[schemars(with = "hex::Hex<isize>")]
+        pub z: isize,
+    }
+
+    impl Default for OffsetsConfig {
+        fn default() -> Self {
+            Self { x: 0, y: 4, z: 8 }
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub enum PointerTypeConfig {
+        Absolute(
+            #[serde(with = "hex")]
+            #[schemars(with = "hex::Hex<usize>")]
+            NonZeroUsize,
+        ),
+        Relative(RelativePointer),
+        /// A static pointer which is followed through several dereferences, re-resolved on every access.
+        Chain(PointerChain),
+    }
+
+    impl PointerTypeConfig {
+        pub fn get_non_null_ptr(&self, platform: &dyn Platform) -> eyre::Result<NonZeroUsize> {
+            let ptr = match self {
+                PointerTypeConfig::Absolute(abs) => *abs,
+                PointerTypeConfig::Relative(rel) => {
+                    NonZeroUsize::new(rel.resolve(platform)?).context("Invalid relative pointer")?
+                }
+                PointerTypeConfig::Chain(chain) => {
+                    NonZeroUsize::new(chain.resolve(platform)?).context("Pointer chain resolved to null")?
+                }
+            };
+
+            Ok(ptr)
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct PointerChain {
+        /// The static start of the chain.
+        pub base: RelativePointer,
+        /// The offsets which are added after every dereference, starting with the pointer stored at `base`.
+        #[serde(with = "hex::list")]
+        #[schemars(with = "Vec<hex::Hex<isize>>")]
+        pub offsets: Vec<isize>,
+    }
+
+    impl PointerChain {
+        /// Resolve the chain in the process of the given `platform`.
+        pub fn resolve(&self, platform: &dyn Platform) -> eyre::Result<usize> {
+            self.follow(self.base.resolve(platform)?, &*platform.memory())
+        }
+
+        /// Follow the chain in the given `memory`, where `base_address` is the already resolved `base`.
+        pub fn follow(&self, base_address: usize, memory: &dyn MemoryAccess) -> eyre::Result<usize> {
+            self.offsets.iter().try_fold(base_address, |address, offset| {
+                let ptr = memory.read_pointer(address)?;
+                eyre::ensure!(
+                    ptr != 0,
+                    "Pointer chain `{self}` contains a null pointer at {address:#X}"
+                );
+                Ok(ptr.wrapping_add_signed(*offset))
+            })
+        }
+    }
+
+    impl Display for PointerChain {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.base.0)?;
+            for offset in &self.offsets {
+                if offset.is_negative() {
+                    write!(f, " -> -{:X}", offset.unsigned_abs())?;
+                } else {
+                    write!(f, " -> {offset:X}")?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// A pointer relative to the base of a module, in the `module+offset` format, e.g. `sekiro.exe+3D7A1E0`.
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct RelativePointer(String);
+
+    impl RelativePointer {
+        pub fn new(module_name: &str, offset: usize) -> Self {
+            Self(format!("{module_name}+{offset:X}"))
+        }
+
+        /// Resolve the absolute address in the process of the given `platform`.
+        pub fn resolve(&self, platform: &dyn Platform) -> eyre::Result<usize> {
+            let module_name = self.module_name()?;
+            let module = platform
+                .module(module_name)
+                .with_context(|| format!("Module `{module_name}` is not loaded"))?;
+            Ok(module.base + self.offset()?)
+        }
+
+        pub fn module_name(&self) -> eyre::Result<&str> {
+            let (module_name, _) = self.0.split_once('+').context("Invalid relative pointer syntax")?;
+            Ok(module_name)
+        }
+
+        pub fn offset(&self) -> eyre::Result<usize> {
+            let (_, offset) = self.0.split_once('+').context("Invalid relative pointer syntax")?;
+            Ok(usize::from_str_radix(offset, 16)?)
+        }
+    }
+
+    #[derive(
+        Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Hash,
+    )]
+    pub enum Register {
+        Rax,
+        Rbx,
+        Rcx,
+        Rdx,
+        Rsi,
+        Rdi,
+        Rbp,
+        Rsp,
+        R8,
+        R9,
+        R10,
+        R11,
+        R12,
+        R13,
+        R14,
+        R15,
+        Rip,
+    }
+
+    impl Register {
+        pub fn to_value(&self, ctx: &dyn RegisterContext) -> u64 {
+            ctx.register(*self)
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub struct Filter {
+        pub compare: Register,
+        pub comparison: Comparison,
+        #[serde(with = "hex")]
+        #[schemars(with = "hex::Hex<usize>")]
+        pub compare_to: usize,
+    }
+
+    impl Filter {
+        /// Whether a hit with the given register state should be processed.
+        pub fn matches(&self, ctx: &dyn RegisterContext) -> bool {
+            let value = self.compare.to_value(ctx) as usize;
+
+            match self.comparison {
+                Comparison::Equal => value == self.compare_to,
+                Comparison::NEqual => value != self.compare_to,
+                Comparison::Gt => value > self.compare_to,
+                Comparison::Lt => value < self.compare_to,
+            }
+        }
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Clone, PartialEq, PartialOrd)]
+    pub enum Comparison {
+        Equal,
+        NEqual,
+        Gt,
+        Lt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MockMemory;
+    use crate::migrations::Versioned;
+    use crate::platform::MockPlatform;
+    use crate::plugins::context::MockRegisters;
+
+    /// `game.exe` contains `0F 28` at `0x1001` and `0F 29` at `0x1004`.
+    fn platform() -> Arc<MockPlatform> {
+        platform_with(|_| {})
+    }
+
+    fn platform_with(setup: impl FnOnce(&mut MockMemory)) -> Arc<MockPlatform> {
+        let mut memory = MockMemory::new();
+        memory.map_bytes(0x1000, vec![0x90, 0x0F, 0x28, 0x90, 0x0F, 0x29], false);
+        memory.add_module("game.exe", 0x1000, 6);
+        setup(&mut memory);
+        Arc::new(MockPlatform::new(Arc::new(memory), Some("game.exe")))
+    }
+
+    fn write_config(file: &Path, position: &str) {
+        let config = format!(
+            r#"version = 1
+            [identifiers]
+            plugin_name = "Test"
+            expected_exe_name = "game.exe"
+            {position}
+            [pointer_offsets]
+            x = 0
+            y = 4
+            z = 8"#
+        );
+        std::fs::write(file, config).unwrap();
+    }
+
+    fn intercept(signature: &str) -> String {
+        format!(
+            r#"[position.InterceptPtr]
+            intercept_signature = "{signature}"
+            register = "Rcx""#
+        )
+    }
+
+    #[test]
+    fn reload_moves_hook() {
+        let platform = platform();
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("plugin.toml");
+
+        write_config(&file, &intercept("0F 28"));
+        let mut plugin = ConfigBasedPlugin::new(&file, platform.clone()).unwrap();
+        plugin.start().unwrap();
+        assert_eq!(platform.hit(0x1001, &MockRegisters::new()), 1);
+
+        write_config(&file, &intercept("0F 29"));
+        plugin.reload_config().unwrap();
+        assert_eq!(platform.attached(), 1);
+        assert_eq!(platform.hit(0x1001, &MockRegisters::new()), 0);
+        assert_eq!(
+            platform.hit(0x1004, &MockRegisters::new().with(Register::Rcx, 0x2000)),
+            1
+        );
+        assert_eq!(plugin.captured_pointer().unwrap().address, 0x2000);
+    }
+
+    #[test]
+    fn reload_keeps_hook_on_missing_signature() {
+        let platform = platform();
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("plugin.toml");
+
+        write_config(&file, &intercept("0F 28"));
+        let mut plugin = ConfigBasedPlugin::new(&file, platform.clone()).unwrap();
+        plugin.start().unwrap();
+
+        write_config(&file, &intercept("FF FF"));
+        assert!(plugin.reload_config().is_err());
+        assert_eq!(platform.hit(0x1001, &MockRegisters::new()), 1);
+        assert!(matches!(
+            &plugin.config().position,
+            GenericPositionConfig::InterceptPtr(intercept) if intercept.intercept_signature == "0F 28"
+        ));
+    }
+
+    #[test]
+    fn reload_switches_to_given_pointer() {
+        let platform = platform();
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("plugin.toml");
+
+        write_config(&file, &intercept("0F 28"));
+        let mut plugin = ConfigBasedPlugin::new(&file, platform.clone()).unwrap();
+        plugin.start().unwrap();
+
+        write_config(
+            &file,
+            r#"[position.AbsolutePtr]
+            Absolute = "0x3000""#,
+        );
+        plugin.reload_config().unwrap();
+        assert_eq!(platform.attached(), 0);
+        assert_eq!(plugin.player_ptr(), Some(0x3000));
+        assert!(plugin.captured_pointer().is_none());
+    }
+
+    #[test]
+    fn load_all_skips_broken_configs() {
+        let directory = tempfile::tempdir().unwrap();
+        let plugins = directory.path().join(PLUGIN_DIRECTORY_NAME);
+        std::fs::create_dir(&plugins).unwrap();
+
+        std::fs::write(
+            directory.path().join(SKIP_PLUGIN_FILENAME),
+            r#"{
+                "version": 1,
+                "identifiers": { "plugin_name": "Legacy", "expected_exe_name": "game.exe" },
+                "position": { "AbsolutePtr": { "Absolute": "0x3000" } },
+                "pointer_offsets": { "x": 0, "y": 4, "z": 8 }
+            }"#,
+        )
+        .unwrap();
+        write_config(&plugins.join("a.toml"), &intercept("0F 28"));
+        std::fs::write(
+            plugins.join("b.json5"),
+            r#"{
+                version: 1,
+                identifiers: { plugin_name: "Json5", expected_exe_name: "game.exe" },
+                position: { AbsolutePtr: { Absolute: "0x3000" } },
+                pointer_offsets: { x: 0, y: 4, z: 8 },
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(plugins.join("broken.toml"), "version = 1\n[identifiers").unwrap();
+        std::fs::write(plugins.join("notes.txt"), "Not a plugin").unwrap();
+
+        let platform: Arc<dyn Platform> = platform();
+        let names = ConfigBasedPlugin::load_all(directory.path(), &platform)
+            .unwrap()
+            .iter()
+            .map(|plugin| plugin.config().identifiers.plugin_name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Legacy", "Test", "Json5"]);
+    }
+
+    fn plugin(platform: &Arc<MockPlatform>, position: &str) -> ConfigBasedPlugin {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("plugin.toml");
+        write_config(&file, position);
+
+        let config = ConfigBasedPlugin::load_config(&file).unwrap();
+        ConfigBasedPlugin::from_config(config, platform.clone())
+    }
+
+    fn filtered(comparison: Comparison, compare_to: usize) -> GenericCoordinateIntercept {
+        GenericCoordinateIntercept {
+            position_ptr: CoordinatePtr::default(),
+            config: Arc::new(Mutex::new(InterceptConfig {
+                intercept_signature: "0F 28".into(),
+                register: Register::Rcx,
+                filter: Some(Filter {
+                    compare: Register::Rdx,
+                    comparison,
+                    compare_to,
+                }),
+            })),
+        }
+    }
+
+    fn captured(intercept: &GenericCoordinateIntercept) -> Option<usize> {
+        intercept.position_ptr.lock().unwrap().map(|ptr| ptr.address)
+    }
+
+    #[test]
+    fn on_hit_captures_register() {
+        let platform = platform();
+        let mut plugin = plugin(&platform, &intercept("0F 28"));
+        plugin.start().unwrap();
+        assert!(plugin.captured_pointer().is_none());
+
+        platform.hit(0x1001, &MockRegisters::new().with(Register::Rcx, 0x2000));
+        let first = plugin.captured_pointer().unwrap();
+        assert_eq!(first.address, 0x2000);
+
+        // Hitting the same pointer again only refreshes it.
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        platform.hit(0x1001, &MockRegisters::new().with(Register::Rcx, 0x2000));
+        let second = plugin.captured_pointer().unwrap();
+        assert_eq!(second.address, 0x2000);
+        assert!(second.last_hit > first.last_hit);
+
+        platform.hit(0x1001, &MockRegisters::new().with(Register::Rcx, 0x4000));
+        assert_eq!(plugin.captured_pointer().unwrap().address, 0x4000);
+    }
+
+    #[test]
+    fn filters() {
+        let cases = [
+            (Comparison::Equal, 0x10, [false, true, false]),
+            (Comparison::NEqual, 0x10, [true, false, true]),
+            (Comparison::Gt, 0x10, [false, false, true]),
+            (Comparison::Lt, 0x10, [true, false, false]),
+        ];
+
+        for (comparison, compare_to, expected) in cases {
+            for (rdx, expected) in [0x8, 0x10, 0x18].into_iter().zip(expected) {
+                let mut intercept = filtered(comparison.clone(), compare_to);
+                let ctx = MockRegisters::new()
+                    .with(Register::Rcx, 0x2000)
+                    .with(Register::Rdx, rdx);
+                intercept.on_hit(&ctx);
+
+                assert_eq!(
+                    captured(&intercept).is_some(),
+                    expected,
+                    "{comparison:?} {compare_to:#X} with Rdx = {rdx:#X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn filtered_hit_keeps_pointer() {
+        let mut intercept = filtered(Comparison::Equal, 1);
+        intercept.on_hit(&MockRegisters::new().with(Register::Rcx, 0x2000).with(Register::Rdx, 1));
+        intercept.on_hit(&MockRegisters::new().with(Register::Rcx, 0x4000).with(Register::Rdx, 2));
+
+        assert_eq!(captured(&intercept), Some(0x2000));
+    }
+
+    #[test]
+    fn coordinates_through_intercept() {
+        let platform = platform_with(|memory| memory.map(0x2000, 0x100, true));
+        let mut plugin = plugin(&platform, &intercept("0F 28"));
+        plugin.start().unwrap();
+
+        assert_eq!(plugin.get_current_coordinates().unwrap(), None);
+        assert!(plugin
+            .set_current_coordinates(PlayerCoordinates { x: 1.0, y: 2.0, z: 3.0 })
+            .is_err());
+
+        platform.hit(0x1001, &MockRegisters::new().with(Register::Rcx, 0x2000));
+        let coordinates = PlayerCoordinates { x: 1.0, y: 2.0, z: 3.0 };
+        plugin.set_current_coordinates(coordinates).unwrap();
+        assert_eq!(plugin.get_current_coordinates().unwrap(), Some(coordinates));
+        assert_eq!(platform.memory().read_f32(0x2004).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn coordinates_through_given_pointer() {
+        let platform = platform_with(|memory| memory.map(0x3000, 0x100, true));
+        platform.memory().write_f32(0x3008, 5.0).unwrap();
+        let mut plugin = plugin(
+            &platform,
+            r#"[position.AbsolutePtr]
+            Absolute = "0x3000""#,
+        );
+        plugin.start().unwrap();
+
+        assert_eq!(
+            plugin.get_current_coordinates().unwrap(),
+            Some(PlayerCoordinates { x: 0.0, y: 0.0, z: 5.0 })
+        );
+
+        // The pointer is never captured, so it never goes stale either.
+        assert!(plugin.captured_pointer().is_none());
+    }
+
+    #[test]
+    fn coordinates_through_fallback_chain() {
+        // `data.dll+0` points to the player.
+        let platform = platform_with(|memory| {
+            memory.map(0x2000, 0x100, true);
+            memory.map_bytes(0x5000, 0x2000usize.to_ne_bytes().to_vec(), false);
+            memory.add_module("data.dll", 0x5000, 0x8);
+        });
+        platform.memory().write_f32(0x2000, 7.0).unwrap();
+
+        let mut plugin = plugin(&platform, &intercept("0F 28"));
+        plugin.start().unwrap();
+        plugin.set_fallback_chains(vec![PointerChain {
+            base: RelativePointer::new("data.dll", 0),
+            offsets: vec![0],
+        }]);
+
+        assert_eq!(plugin.get_current_coordinates().unwrap().map(|c| c.x), Some(7.0));
+    }
+
+    const LIES_OF_P: &str = "Lies of P Skip Runback";
+
+    #[test]
+    fn extend_merges_objects() {
+        let config = GenericConfig::extend(
+            LIES_OF_P,
+            serde_json::json!({
+                "identifiers": { "plugin_name": "Lies of P (patched)" },
+                "position": { "InterceptPtr": { "register": "Rcx" } },
+                "pointer_offsets": { "x": "0x10" },
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(config.identifiers.plugin_name, "Lies of P (patched)");
+        assert_eq!(config.identifiers.expected_exe_name.as_deref(), Some("LOP.exe"));
+        let GenericPositionConfig::InterceptPtr(intercept) = &config.position else {
+            panic!("Expected an intercept, got {:?}", config.position);
+        };
+        assert_eq!(intercept.register, Register::Rcx);
+        assert_eq!(intercept.intercept_signature, "41 0F 10 89 C0 01 00 00 48 8D 44 24 28");
+        assert_eq!((config.pointer_offsets.x, config.pointer_offsets.y), (0x10, 0x1C4));
+    }
+
+    #[test]
+    fn extend_switches_variant() {
+        let config = GenericConfig::extend(
+            LIES_OF_P,
+            serde_json::json!({ "position": { "AbsolutePtr": { "Absolute": "0x3000" } } }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.position,
+            GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Absolute(0x3000.try_into().unwrap()))
+        );
+    }
+
+    #[test]
+    fn extend_reports_path() {
+        let error = GenericConfig::extend(LIES_OF_P, serde_json::json!({ "pointer_offsets": { "y": true } }))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`pointer_offsets.y`"), "{error}");
+
+        let error = GenericConfig::extend("Unknown", serde_json::json!({}))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(LIES_OF_P), "{error}");
+    }
+
+    #[test]
+    fn merge_replaces_arrays() {
+        let mut base = serde_json::json!({
+            "base": "game.exe+10",
+            "offsets": ["0x10", "0x20"],
+            "nested": { "a": 1, "b": { "c": 2 } },
+        });
+        config::merge_json(
+            &mut base,
+            serde_json::json!({ "offsets": ["0x30"], "nested": { "b": { "d": 3 } } }),
+        );
+
+        assert_eq!(
+            base,
+            serde_json::json!({
+                "base": "game.exe+10",
+                "offsets": ["0x30"],
+                "nested": { "a": 1, "b": { "c": 2, "d": 3 } },
+            })
+        );
+    }
+
+    #[test]
+    fn parse_upgrades_unversioned() {
+        let config = GenericConfig::parse(
+            Format::Json,
+            br#"{
+                "identifiers": { "plugin_name": "Test", "expected_module": null, "expected_exe_name": "game.exe" },
+                "position": { "InterceptPtr": { "intercept_signature": "0F 28", "register": "Rcx", "filter": null } },
+                "pointer_offsets": { "x": 128, "y": 132, "z": 136 }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.version, GenericConfig::VERSION);
+        assert_eq!(config.pointer_offsets.x, 0x80);
+    }
+
+    #[test]
+    fn validate() {
+        let valid = GenericConfig::default();
+        valid.validate().unwrap();
+
+        let invalid = |modify: fn(&mut GenericConfig), expected: &str| {
+            let mut config = valid.clone();
+            modify(&mut config);
+            let error = config.validate().unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        };
+
+        invalid(|c| c.identifiers.plugin_name = " ".into(), "plugin_name");
+        invalid(
+            |c| {
+                c.identifiers.expected_module = None;
+                c.identifiers.expected_exe_name = None;
+            },
+            "expected_exe_name",
+        );
+        invalid(
+            |c| {
+                c.position = GenericPositionConfig::InterceptPtr(InterceptConfig {
+                    intercept_signature: "0F 2G".into(),
+                    register: Register::Rcx,
+                    filter: None,
+                })
+            },
+            "intercept_signature",
+        );
+        invalid(
+            |c| {
+                c.position = GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Chain(PointerChain {
+                    base: serde_json::from_str(r#""game.exe""#).unwrap(),
+                    offsets: vec![0x10],
+                }))
+            },
+            "`game.exe`",
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! (De)serialization of integers as hex strings, for use with `#[serde(with = "crate::plugins::hex")]`.
+//!
+//! Hex strings (`"0x80"`, `"-0x80"`), decimal strings (`"128"`) and plain numbers are all accepted, values are always
+//! written as hex strings.
+//!
+//! # Example
+//! ```rust
+//! # use skip_core::plugins::hex::Hex;
+//! let Hex(offset) = serde_json::from_str::<Hex<isize>>(r#""-0x10""#).unwrap();
+//! assert_eq!(offset, -0x10);
+//! assert_eq!(serde_json::to_string(&Hex(offset)).unwrap(), r#""-0x10""#);
+//! ```
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation, SubschemaValidation};
+use schemars::JsonSchema;
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An integer which can be written as a hex string.
+pub trait HexValue: Sized + Copy {
+    fn to_i128(self) -> i128;
+
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! hex_value {
+    ($($ty:ty),*) => {
+        $(impl HexValue for $ty {
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                value.try_into().ok()
+            }
+        })*
+    };
+}
+
+hex_value!(isize, usize, i32, u32, i64, u64);
+
+impl HexValue for NonZeroUsize {
+    fn to_i128(self) -> i128 {
+        self.get() as i128
+    }
+
+    fn from_i128(value: i128) -> Option<Self> {
+        NonZeroUsize::new(value.try_into().ok()?)
+    }
+}
+
+/// Wrapper to (de)serialize `T` as a hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hex<T>(pub T);
+
+impl<T: HexValue> Serialize for Hex<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: HexValue> Deserialize<'de> for Hex<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Hex)
+    }
+}
+
+impl<T: HexValue> JsonSchema for Hex<T> {
+    fn schema_name() -> String {
+        "HexInteger".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        let hex_string = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^-?(0[xX][0-9a-fA-F]+|[0-9]+)$".into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let integer = SchemaObject {
+            instance_type: Some(InstanceType::Integer.into()),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![hex_string.into(), integer.into()]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+pub fn serialize<T: HexValue, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    let value = value.to_i128();
+    let sign = if value < 0 { "-" } else { "" };
+
+    serializer.serialize_str(&format!("{sign}0x{:X}", value.unsigned_abs()))
+}
+
+pub fn deserialize<'de, T: HexValue, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    deserializer.deserialize_any(HexVisitor(PhantomData))
+}
+
+/// (De)serialization of a list of integers as hex strings, for use with `#[serde(with = "crate::plugins::hex::list")]`.
+pub mod list {
+    use super::*;
+
+    pub fn serialize<T: HexValue, S: Serializer>(values: &[T], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|value| Hex(*value)))
+    }
+
+    pub fn deserialize<'de, T: HexValue, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<T>, D::Error> {
+        let values = Vec::<Hex<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|Hex(value)| value).collect())
+    }
+}
+
+/// Parse `0x80`, `-0x80` or `128`.
+pub fn parse(value: &str) -> Option<i128> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let (digits, radix) = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (value, 10),
+    };
+    // `from_str_radix` accepts a sign of its own, which would allow a second one.
+    if digits.starts_with(['-', '+']) {
+        return None;
+    }
+    let parsed = i128::from_str_radix(digits, radix).ok()?;
+
+    Some(if negative { -parsed } else { parsed })
+}
+
+struct HexVisitor<T>(PhantomData<T>);
+
+impl<T: HexValue> HexVisitor<T> {
+    fn convert<E: Error>(value: i128) -> Result<T, E> {
+        T::from_i128(value).ok_or_else(|| E::custom(format_args!("{value} is out of range")))
+    }
+}
+
+impl<T: HexValue> Visitor<'_> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an integer, or a hex string like \"0x80\"")
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+        Self::convert(v as i128)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Self::convert(v as i128)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        let value = parse(v).ok_or_else(|| E::custom(format_args!("`{v}` is not a valid hex or decimal number")))?;
+        Self::convert(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json<T: HexValue>(json: &str) -> Result<T, serde_json::Error> {
+        serde_json::from_str::<Hex<T>>(json).map(|Hex(value)| value)
+    }
+
+    #[test]
+    fn deserialize_formats() {
+        for input in [r#""0x80""#, r#""0X80""#, r#""128""#, "128", r#"" 0x80 ""#] {
+            assert_eq!(from_json::<isize>(input).unwrap(), 0x80, "{input}");
+        }
+        assert_eq!(from_json::<isize>(r#""-0x10""#).unwrap(), -0x10);
+        assert_eq!(from_json::<isize>("-16").unwrap(), -0x10);
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        assert!(from_json::<usize>(r#""-0x10""#).is_err());
+        assert!(from_json::<usize>(r#""0xZZ""#).is_err());
+        assert!(from_json::<isize>(r#""--0x10""#).is_err());
+        assert!(from_json::<isize>(r#""-+16""#).is_err());
+        assert!(from_json::<isize>(r#""0x-10""#).is_err());
+        assert!(from_json::<isize>(r#""0x+10""#).is_err());
+        assert!(from_json::<u32>(r#""0x100000000""#).is_err());
+        assert!(from_json::<NonZeroUsize>("0").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = serde_json::to_string(&Hex(0x1C8usize)).unwrap();
+        assert_eq!(json, r#""0x1C8""#);
+        assert_eq!(from_json::<usize>(&json).unwrap(), 0x1C8);
+
+        let json = serde_json::to_string(&Hex(i64::MIN)).unwrap();
+        assert_eq!(json, r#""-0x8000000000000000""#);
+        assert_eq!(from_json::<i64>(&json).unwrap(), i64::MIN);
+    }
+
+    #[test]
+    fn list() {
+        #[derive(Serialize, Deserialize)]
+        struct Offsets(#[serde(with = "super::list")] Vec<isize>);
+
+        let Offsets(offsets) = serde_json::from_str(r#"["0x10", -8, "24"]"#).unwrap();
+        assert_eq!(offsets, [0x10, -8, 24]);
+        assert_eq!(
+            serde_json::to_string(&Offsets(offsets)).unwrap(),
+            r#"["0x10","-0x8","0x18"]"#
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::config::PluginSelectionConfig;
+use crate::platform::{HitListener, Hook, Platform};
+use crate::plugins::generic::ConfigBasedPlugin;
+
+pub mod context;
+pub mod generic;
+pub mod hex;
+mod sekiro;
+
+/// The plugin database of all supported games which only need a [generic::GenericConfig], compiled into the binary.
+static BUILTIN_PLUGINS: &str = include_str!("builtin_plugins.json");
+
+static BUILTIN_CONFIGS: Lazy<Vec<generic::GenericConfig>> = Lazy::new(load_builtin_configs);
+
+/// The player pointer as captured by an intercept, shared between the plugin and its listener.
+pub type CoordinatePtr = Arc<Mutex<Option<CapturedPointer>>>;
+
+/// Get all plugins which could apply.
+///
+/// User plugins come first, built-in plugins which are extended by a user plugin are left out.
+pub fn get_all_plugins(search_path: &Path, platform: &Arc<dyn Platform>) -> Vec<Box<dyn SkipPlugin>> {
+    let user_plugins = ConfigBasedPlugin::load_all(search_path, platform).unwrap_or_default();
+    let overridden = user_plugins
+        .iter()
+        .filter_map(|plugin| plugin.config().extends.clone())
+        .collect::<HashSet<_>>();
+
+    let mut generic_skips = user_plugins
+        .into_iter()
+        .map(|plugin| Box::new(plugin) as Box<dyn SkipPlugin>)
+        .collect::<Vec<_>>();
+    let builtins = builtin_configs()
+        .iter()
+        .map(|config| Box::new(ConfigBasedPlugin::from_config(config.clone(), platform.clone())) as Box<dyn SkipPlugin>)
+        .chain([Box::new(sekiro::SekiroPlugin::new(platform.clone())) as Box<dyn SkipPlugin>]);
+
+    for builtin in builtins {
+        let name = builtin.identifiers().plugin_name;
+        if overridden.contains(&name) {
+            log::info!("Built-in plugin `{name}` is overridden by a user plugin");
+        } else {
+            generic_skips.push(builtin);
+        }
+    }
+
+    generic_skips
+}
+
+/// The names of all plugins which [get_all_plugins] could return for `search_path`, without creating the plugins.
+///
+/// See [plugin_identifiers].
+pub fn plugin_names(search_path: &Path) -> Vec<String> {
+    plugin_identifiers(search_path)
+        .into_iter()
+        .map(|identifiers| identifiers.plugin_name)
+        .collect()
+}
+
+/// The identifiers of all plugins which [get_all_plugins] could return for `search_path`, without creating the plugins.
+///
+/// The plugin configs are only read, unlike [get_all_plugins] this doesn't upgrade them on disk. Configs which fail to
+/// parse are reported and skipped.
+pub fn plugin_identifiers(search_path: &Path) -> Vec<PluginIdentifiers> {
+    let user_configs = ConfigBasedPlugin::read_configs(search_path).unwrap_or_else(|e| {
+        log::warn!("Failed to list the plugin configs in {search_path:?}: {e}");
+        Vec::new()
+    });
+
+    user_configs
+        .into_iter()
+        .filter_map(|(file, config)| {
+            config
+                .inspect_err(|e| log::warn!("Failed to read plugin config {file:?}, ignoring it: {e}"))
+                .ok()
+        })
+        .map(|config| config.identifiers)
+        .chain(builtin_configs().iter().map(|config| config.identifiers.clone()))
+        .chain([sekiro::identifiers()])
+        .collect()
+}
+
+/// The configs of the built-in plugins which can be extended by user plugins, see [generic::GenericConfig::extends].
+pub fn builtin_configs() -> &'static [generic::GenericConfig] {
+    &BUILTIN_CONFIGS
+}
+
+/// Parse [BUILTIN_PLUGINS] the same way as user plugins, entries which fail to parse are reported and skipped.
+fn load_builtin_configs() -> Vec<generic::GenericConfig> {
+    let entries = match serde_json::from_str::<Vec<serde_json::Value>>(BUILTIN_PLUGINS) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::error!("The built-in plugin database is invalid, no built-in plugins are available: {e}");
+            return Vec::new();
+        }
+    };
+
+    entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let config = if entry.get("extends").is_some_and(|extends| !extends.is_null()) {
+                Err(eyre::eyre!("Built-in plugins can't extend other plugins"))
+            } else {
+                generic::GenericConfig::parse(crate::format::Format::Json, entry.to_string().as_bytes())
+            };
+
+            config
+                .inspect_err(|e| log::error!("Skipping built-in plugin {index}, it's invalid: {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+pub trait SkipPlugin {
+    /// Retrieve the identifiers which will be used to check whether the current exe matches, unless the match function has been replaced.
+    fn identifiers(&self) -> PluginIdentifiers;
+
+    /// Indicate to this plugin that it should run, apply its hooks, etc.
+    fn start(&mut self) -> eyre::Result<()>;
+
+    /// Check which of this plugin's identifiers match the current process.
+    fn match_identifiers(&self, platform: &dyn Platform) -> PluginMatch {
+        self.identifiers().matches(platform)
+    }
+
+    /// Check whether this plugin should be applied
+    fn should_apply(&self, platform: &dyn Platform) -> bool {
+        self.match_identifiers(platform).is_match()
+    }
+
+    /// Return the current player coordinates.
+    ///
+    /// Can return [None] if the player pointer hasn't been identifier yet.
+    fn get_current_coordinates(&mut self) -> eyre::Result<Option<PlayerCoordinates>>;
+
+    /// Set the given coordinates as the new player coordinates.
+    ///
+    /// # Errors
+    ///
+    /// If the location could not be set
+    fn set_current_coordinates(&mut self, coordinates: PlayerCoordinates) -> eyre::Result<()>;
+
+    /// The pointer to the player struct as captured by this plugin's intercept, if any.
+    ///
+    /// Used as the target of pointer scans, and to detect stale pointers.
+    fn captured_pointer(&self) -> Option<CapturedPointer> {
+        None
+    }
+
+    /// Provide validated pointer chains which can be used to find the player while no pointer has been captured yet.
+    fn set_fallback_chains(&mut self, _chains: Vec<generic::PointerChain>) {}
+
+    /// Force the current plugins to reload their configs, and restart their interceptors if needed.
+    fn reload_config(&mut self) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapturedPointer {
+    pub address: usize,
+    /// The last time the intercept was hit with this pointer.
+    pub last_hit: Instant,
+}
+
+impl CapturedPointer {
+    pub fn new(address: usize) -> Self {
+        Self {
+            address,
+            last_hit: Instant::now(),
+        }
+    }
+
+    /// The time since the intercept last saw this pointer.
+    pub fn age(&self) -> Duration {
+        self.last_hit.elapsed()
+    }
+
+    /// Whether this pointer hasn't been seen by the intercept for longer than `timeout`.
+    ///
+    /// A pointer which isn't refreshed any more usually means the struct it points to has been freed (e.g. after a
+    /// loading screen).
+    pub fn is_stale(&self, timeout: Duration) -> bool {
+        self.age() > timeout
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, schemars::JsonSchema, PartialOrd, PartialEq)]
+pub struct PlayerCoordinates {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C)]
+pub struct CPlayerCoordinates {
+    x: f32,
+    z: f32,
+    y: f32,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema, PartialEq, PartialOrd)]
+pub struct PluginIdentifiers {
+    pub plugin_name: String,
+    pub expected_module: Option<String>,
+    pub expected_exe_name: Option<String>,
+}
+
+impl PluginIdentifiers {
+    /// Check which of the identifiers match the current process.
+    pub fn matches(&self, platform: &dyn Platform) -> PluginMatch {
+        let main_module = platform.main_module();
+
+        PluginMatch {
+            module: self
+                .expected_module
+                .as_ref()
+                .map(|expected| platform.module(expected).is_some()),
+            exe: self
+                .expected_exe_name
+                .as_ref()
+                .map(|exe| main_module.is_some_and(|module| &module.name == exe)),
+        }
+    }
+}
+
+/// Which of a plugin's [PluginIdentifiers] matched the current process, [None] for identifiers which aren't set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PluginMatch {
+    pub module: Option<bool>,
+    pub exe: Option<bool>,
+}
+
+impl PluginMatch {
+    pub fn is_match(&self) -> bool {
+        self.module == Some(true) || self.exe == Some(true)
+    }
+}
+
+impl std::fmt::Display for PluginMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let describe = |matched: Option<bool>| match matched {
+            Some(true) => "matched",
+            Some(false) => "no match",
+            None => "not set",
+        };
+
+        write!(f, "module {}, exe {}", describe(self.module), describe(self.exe))
+    }
+}
+
+/// Pick the plugins which apply to the current process, and log why every candidate was (not) picked.
+///
+/// A plugin forced by the `selection` config is used even if it doesn't match, disabled plugins are never used. Returns
+/// the indices of the picked plugins in priority order. Warnings are only logged if `report` is set, otherwise the
+/// report is logged at debug level.
+///
+/// # Errors
+///
+/// If the forced plugin doesn't exist.
+pub fn select_plugins(
+    plugins: &[Box<dyn SkipPlugin>],
+    selection: &PluginSelectionConfig,
+    platform: &dyn Platform,
+    report: bool,
+) -> eyre::Result<Vec<usize>> {
+    let names = plugins
+        .iter()
+        .map(|plugin| plugin.identifiers().plugin_name)
+        .collect::<Vec<_>>();
+    if let Some(forced) = &selection.force {
+        eyre::ensure!(
+            names.contains(forced),
+            "Forced plugin `{forced}` doesn't exist, available plugins are: {}",
+            names.join(", ")
+        );
+    }
+    if report {
+        for unknown in selection.disabled.iter().filter(|name| !names.contains(name)) {
+            log::warn!("Disabled plugin `{unknown}` doesn't exist");
+        }
+    }
+
+    let mut selected = Vec::new();
+    let mut candidates = String::from("Plugin candidates:");
+    for (index, (plugin, name)) in plugins.iter().zip(&names).enumerate() {
+        let matched = plugin.match_identifiers(platform);
+
+        let (selected_plugin, verdict) = if selection.disabled.contains(name) {
+            (false, "disabled")
+        } else if let Some(forced) = &selection.force {
+            if forced == name {
+                (true, "forced")
+            } else {
+                (false, "skipped, another plugin is forced")
+            }
+        } else if plugin.should_apply(platform) {
+            (true, "applies")
+        } else {
+            (false, "doesn't apply")
+        };
+        candidates.push_str(&format!("\n  `{name}` ({matched}): {verdict}"));
+
+        if selected_plugin {
+            selected.push(index);
+        }
+    }
+
+    if report {
+        log::info!("{candidates}");
+        if selected.len() > 1 {
+            log::warn!(
+                "Multiple plugins apply, `{}` has priority. Set `plugins.force` or `plugins.disabled` in the config to \
+                choose another",
+                names[selected[0]]
+            );
+        }
+    } else {
+        log::debug!("{candidates}");
+    }
+
+    Ok(selected)
+}
+
+/// Start the first applicable plugin (see [select_plugins]) which starts successfully, logging the reason for every one
+/// that doesn't.
+///
+/// The started plugin is removed from `plugins`, the others are left as is so that starting can be retried later.
+/// Returns [None] if no plugin applies, or if the applicable plugins which failed to start are still waiting for their
+/// `expected_module` to be loaded.
+///
+/// # Errors
+///
+/// If the selection is invalid, or none of the applicable plugins could be started even though their module is loaded.
+pub fn start_plugin(
+    plugins: &mut Vec<Box<dyn SkipPlugin>>,
+    selection: &PluginSelectionConfig,
+    platform: &dyn Platform,
+    report: bool,
+) -> eyre::Result<Option<Box<dyn SkipPlugin>>> {
+    let candidates = select_plugins(plugins, selection, platform, report)?;
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut failures = Vec::new();
+    let mut waiting = false;
+    for index in candidates {
+        let plugin = &mut plugins[index];
+        let name = plugin.identifiers().plugin_name;
+
+        match plugin.start() {
+            Ok(()) => {
+                log::info!("Found `{name}` as the plugin to use for skipping runback");
+                return Ok(Some(plugins.remove(index)));
+            }
+            Err(e) => {
+                log::log!(
+                    if report { log::Level::Warn } else { log::Level::Debug },
+                    "Failed to start plugin `{name}`, trying the next applicable plugin: {e:#}"
+                );
+                // Only matched on the exe name so far, its module (and thus the signature) might not be loaded yet.
+                waiting |= plugin.match_identifiers(platform).module == Some(false);
+                failures.push(format!("`{name}`: {e:#}"));
+            }
+        }
+    }
+
+    if waiting {
+        return Ok(None);
+    }
+    eyre::bail!(
+        "None of the applicable plugins could be started:\n{}",
+        failures.join("\n")
+    )
+}
+
+/// Find the given signature in the given `module` (or the process' main module), and subsequently attach the
+/// `listener` to it.
+///
+/// The returned [Hook] must be kept alive for as long as the listener should stay attached.
+pub fn attach_listener_to_signature(
+    platform: &dyn Platform,
+    signature: &str,
+    module: Option<&str>,
+    listener: impl HitListener + 'static,
+) -> eyre::Result<Hook> {
+    let position_fn_ptr = platform.find_signature(module, signature)?;
+
+    log::info!("Found position modification ptr: {:#X}", position_fn_ptr);
+
+    let hook = platform.attach(position_fn_ptr, Box::new(listener))?;
+
+    log::info!("Initiated interceptor at {position_fn_ptr:#X}");
+
+    Ok(hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Format;
+    use crate::memory::MockMemory;
+    use crate::platform::MockPlatform;
+    use crate::plugins::generic::GenericConfig;
+
+    /// `game.exe` contains `0F 28` at `0x1001`, `late.dll` is only mapped if `module_loaded` is set.
+    fn platform(module_loaded: bool) -> Arc<MockPlatform> {
+        let mut memory = MockMemory::new();
+        memory.map_bytes(0x1000, vec![0x90, 0x0F, 0x28], false);
+        memory.add_module("game.exe", 0x1000, 3);
+        if module_loaded {
+            memory.map_bytes(0x5000, vec![0x90, 0x90], false);
+            memory.add_module("late.dll", 0x5000, 2);
+        }
+        Arc::new(MockPlatform::new(Arc::new(memory), Some("game.exe")))
+    }
+
+    /// A plugin hooking `0F 28` in `late.dll`, which applies to `game.exe`.
+    fn plugins(platform: &Arc<MockPlatform>) -> Vec<Box<dyn SkipPlugin>> {
+        let config = r#"version = 1
+            [identifiers]
+            plugin_name = "Late"
+            expected_exe_name = "game.exe"
+            expected_module = "late.dll"
+            [position.InterceptPtr]
+            intercept_signature = "0F 28"
+            register = "Rcx"
+            [pointer_offsets]
+            x = 0
+            y = 4
+            z = 8"#;
+        let config = GenericConfig::parse(Format::Toml, config.as_bytes()).unwrap();
+        vec![Box::new(ConfigBasedPlugin::from_config(config, platform.clone()))]
+    }
+
+    #[test]
+    fn plugin_names_leaves_files_untouched() {
+        let directory = tempfile::tempdir().unwrap();
+        let plugins = directory.path().join(generic::PLUGIN_DIRECTORY_NAME);
+        std::fs::create_dir(&plugins).unwrap();
+        let unversioned = br#"{
+            "identifiers": { "plugin_name": "Old", "expected_module": null, "expected_exe_name": "game.exe" },
+            "position": { "InterceptPtr": { "intercept_signature": "0F 28", "register": "Rcx", "filter": null } },
+            "pointer_offsets": { "x": 0, "y": 4, "z": 8 }
+        }"#;
+        std::fs::write(plugins.join("old.json"), unversioned).unwrap();
+        std::fs::write(plugins.join("broken.json"), "{").unwrap();
+
+        let names = plugin_names(directory.path());
+        assert!(names.contains(&"Old".to_string()));
+        assert!(names.contains(&sekiro::PLUGIN_NAME.to_string()));
+
+        assert_eq!(std::fs::read(plugins.join("old.json")).unwrap(), unversioned);
+        assert_eq!(
+            std::fs::read_dir(&plugins).unwrap().count(),
+            2,
+            "No backups are written"
+        );
+    }
+
+    #[test]
+    fn start_waits_for_module() {
+        let platform = platform(false);
+        let mut plugins = plugins(&platform);
+        let started = start_plugin(&mut plugins, &Default::default(), &*platform, false).unwrap();
+        assert!(started.is_none());
+        assert_eq!(plugins.len(), 1, "The plugin is kept to retry starting it");
+    }
+
+    #[test]
+    fn start_fails_with_loaded_module() {
+        let platform = platform(true);
+        let mut plugins = plugins(&platform);
+        let error = start_plugin(&mut plugins, &Default::default(), &*platform, false)
+            .err()
+            .unwrap();
+        assert!(format!("{error:#}").contains("`Late`"), "{error:#}");
+    }
+
+    #[test]
+    fn start_fails_on_unknown_forced_plugin() {
+        let platform = platform(false);
+        let mut plugins = plugins(&platform);
+        let selection = PluginSelectionConfig {
+            force: Some("Missing".to_string()),
+            ..Default::default()
+        };
+        let error = start_plugin(&mut plugins, &selection, &*platform, false).err().unwrap();
+        assert!(
+            error.to_string().contains("Forced plugin `Missing` doesn't exist"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn builtin_configs_are_valid() {
+        let entries = serde_json::from_str::<Vec<serde_json::Value>>(BUILTIN_PLUGINS).unwrap();
+        let configs = builtin_configs();
+        assert_eq!(configs.len(), entries.len(), "Every built-in plugin has to parse");
+
+        for config in configs {
+            config.validate().unwrap();
+            assert_eq!(config.extends, None);
+        }
+
+        let names = configs
+            .iter()
+            .map(|config| config.identifiers.plugin_name.as_str())
+            .chain([sekiro::PLUGIN_NAME])
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            names.len(),
+            configs.len() + 1,
+            "Built-in plugin names have to be unique"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Sekiro specific module for demonstration's sake
+
+use std::mem::offset_of;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::memory::MemoryAccess;
+use crate::platform::{HitListener, Hook, Platform};
+use crate::plugins::context::RegisterContext;
+use crate::plugins::generic::Register;
+use crate::plugins::{CPlayerCoordinates, CapturedPointer, CoordinatePtr, PluginIdentifiers, SkipPlugin};
+
+/// Signature of the instruction which exclusively reads from the player coordinates struct.
+pub static READ_FROM_COORDS_SIG: &str = "0F 28 81 80 00 00 00 4D";
+pub static PLUGIN_NAME: &str = "Sekiro Skip Runback";
+
+pub fn identifiers() -> PluginIdentifiers {
+    PluginIdentifiers {
+        plugin_name: PLUGIN_NAME.to_string(),
+        expected_module: Some("sekiro.exe".to_string()),
+        expected_exe_name: Some("sekiro.exe".to_string()),
+    }
+}
+
+pub struct SekiroPlugin {
+    coords: CoordinatePtr,
+    hook: Option<Hook>,
+    memory: Arc<dyn MemoryAccess>,
+    platform: Arc<dyn Platform>,
+}
+
+impl SekiroPlugin {
+    pub fn new(platform: Arc<dyn Platform>) -> Self {
+        Self {
+            coords: Default::default(),
+            hook: None,
+            memory: platform.memory(),
+            platform,
+        }
+    }
+
+    /// Create a listener which updates this plugin's player pointer when hit.
+    pub fn create_listener(&self) -> SekiroCoordinatesIntercept {
+        SekiroCoordinatesIntercept(self.coords.clone())
+    }
+}
+
+impl SkipPlugin for SekiroPlugin {
+    fn identifiers(&self) -> PluginIdentifiers {
+        identifiers()
+    }
+
+    fn start(&mut self) -> eyre::Result<()> {
+        let listener = self.create_listener();
+        self.hook = Some(super::attach_listener_to_signature(
+            &*self.platform,
+            READ_FROM_COORDS_SIG,
+            None,
+            listener,
+        )?);
+
+        Ok(())
+    }
+
+    fn get_current_coordinates(&mut self) -> eyre::Result<Option<crate::plugins::PlayerCoordinates>> {
+        let Some(coords) = *self.coords.lock().unwrap() else {
+            return Ok(None);
+        };
+        let base = coords.address;
+
+        Ok(Some(super::PlayerCoordinates {
+            x: self.memory.read_f32(base + offset_of!(CPlayerCoordinates, x))?,
+            y: self.memory.read_f32(base + offset_of!(CPlayerCoordinates, y))?,
+            z: self.memory.read_f32(base + offset_of!(CPlayerCoordinates, z))?,
+        }))
+    }
+
+    fn set_current_coordinates(&mut self, target: crate::plugins::PlayerCoordinates) -> eyre::Result<()> {
+        if let Some(coords) = *self.coords.lock().unwrap() {
+            let base = coords.address;
+            self.memory
+                .write_f32(base + offset_of!(CPlayerCoordinates, x), target.x)?;
+            self.memory
+                .write_f32(base + offset_of!(CPlayerCoordinates, y), target.y)?;
+            self.memory
+                .write_f32(base + offset_of!(CPlayerCoordinates, z), target.z)?;
+
+            Ok(())
+        } else {
+            eyre::bail!("Pointer not initialised")
+        }
+    }
+
+    fn captured_pointer(&self) -> Option<CapturedPointer> {
+        // Note that this already points to the coordinates, rather than the start of the player struct.
+        *self.coords.lock().unwrap()
+    }
+}
+
+pub struct SekiroCoordinatesIntercept(CoordinatePtr);
+
+impl SekiroCoordinatesIntercept {
+    /// Process a single hit of the intercepted instruction.
+    pub fn handle_hit(&mut self, ctx: &dyn RegisterContext) {
+        let base_ptr = ctx.register(Register::Rcx);
+        let position_ptr = base_ptr + 0x80;
+        let coords = position_ptr as usize;
+
+        let mut lock = self.0.lock().unwrap();
+
+        match lock.as_mut() {
+            Some(captured) if captured.address == coords => captured.last_hit = Instant::now(),
+            _ => {
+                let old = lock.map(|ptr| ptr.address).unwrap_or_default();
+                *lock = Some(CapturedPointer::new(coords));
+                log::trace!("Updated Sekiro player pointer from `{old:#X}` to {:#X}", coords);
+            }
+        }
+    }
+}
+
+impl HitListener for SekiroCoordinatesIntercept {
+    fn on_hit(&mut self, ctx: &dyn RegisterContext) {
+        self.handle_hit(ctx);
+    }
+}
@@ -0,0 +1,286 @@
+//! The main loop shared by all hosts.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eyre::Context;
+
+use crate::app::SkipApp;
+use crate::config::{DeferredStartConfig, SkipConfig};
+use crate::discovery::CoordinateDiscovery;
+use crate::keys::KeyInput;
+use crate::platform::Platform;
+use crate::plugins;
+use crate::plugins::SkipPlugin;
+use crate::watcher::FileWatcher;
+use crate::{config, schema, watcher};
+
+/// Host specific behaviour of the main loop.
+pub trait Host {
+    /// Whether the game currently has focus, keybinds are ignored while it doesn't.
+    fn is_foreground(&self) -> bool {
+        true
+    }
+
+    /// Reload the config from disk, applying any host specific settings which changed compared to `old`.
+    ///
+    /// On failure the previous config stays in use.
+    fn reload_config(&mut self, old: &SkipConfig) -> eyre::Result<SkipConfig>;
+}
+
+pub struct Runner {
+    app: Option<SkipApp>,
+    /// Plugins which are still being retried while no plugin could be started yet.
+    deferred: Option<DeferredStart>,
+    discovery: CoordinateDiscovery,
+    /// Watches the config files while [SkipConfig::hot_reload] is enabled.
+    watcher: Option<FileWatcher>,
+    /// The directory containing the config and plugin configs.
+    config_directory: PathBuf,
+    /// The directory in which waypoints and other data are stored, see [SkipConfig::data_directory].
+    data_directory: PathBuf,
+    platform: Arc<dyn Platform>,
+}
+
+struct DeferredStart {
+    plugins: Vec<Box<dyn SkipPlugin>>,
+    deadline: Instant,
+    next_attempt: Instant,
+    interval: Duration,
+    max_interval: Duration,
+}
+
+impl DeferredStart {
+    fn new(plugins: Vec<Box<dyn SkipPlugin>>, config: &DeferredStartConfig) -> Self {
+        let interval = Duration::from_millis(250);
+        Self {
+            plugins,
+            deadline: Instant::now() + Duration::from_secs_f32(config.timeout.max(0.0)),
+            next_attempt: Instant::now() + interval,
+            interval,
+            max_interval: Duration::from_secs_f32(config.max_interval.max(0.0)),
+        }
+    }
+}
+
+impl Runner {
+    /// Select the plugin which applies to the current game, and start it.
+    ///
+    /// # Errors
+    ///
+    /// If no plugin applies while the discovery tool is disabled, or none of the applicable plugins could be started.
+    pub fn new(
+        config_directory: impl Into<PathBuf>,
+        config: &SkipConfig,
+        platform: Arc<dyn Platform>,
+    ) -> eyre::Result<Self> {
+        let config_directory = config_directory.into();
+        let plugins = plugins::get_all_plugins(&config_directory, &platform);
+
+        Self::with_plugins(config_directory, config, platform, plugins)
+    }
+
+    /// Select the given `plugins` which apply to the current game, and start the first one which starts successfully.
+    ///
+    /// See [plugins::start_plugin] for how the plugin is selected. If no plugin applies yet and
+    /// [SkipConfig::deferred_start] is set, starting is retried in [Runner::run_until] instead.
+    pub fn with_plugins(
+        config_directory: impl Into<PathBuf>,
+        config: &SkipConfig,
+        platform: Arc<dyn Platform>,
+        mut plugins: Vec<Box<dyn SkipPlugin>>,
+    ) -> eyre::Result<Self> {
+        let config_directory = config_directory.into();
+        let data_directory = config.data_directory(&config_directory)?;
+        std::fs::create_dir_all(&data_directory)
+            .with_context(|| format!("Couldn't create the data directory {data_directory:?}"))?;
+        if data_directory != config_directory {
+            log::info!("Storing waypoints and other data in {data_directory:?}");
+            // Saved waypoints reference the schemas next to them.
+            schema::write_schemas(&data_directory)?;
+        }
+
+        let started = plugins::start_plugin(&mut plugins, &config.plugins, &*platform, true);
+
+        let mut app = None;
+        let mut deferred = None;
+        // Only a plugin which doesn't apply yet can be fixed by waiting, errors in the config are reported right away.
+        match (started?, &config.deferred_start) {
+            (Some(plugin), _) => app = Some(SkipApp::new(&data_directory, plugin, platform.clone())?),
+            (None, Some(deferred_conf)) => {
+                log::info!(
+                    "No applicable plugin could be started yet, retrying in the background for up to {}s in case the \
+                    game loads its module later",
+                    deferred_conf.timeout
+                );
+                deferred = Some(DeferredStart::new(plugins, deferred_conf));
+            }
+            // Without a plugin we can still help with finding the player's coordinates.
+            (None, None) if config.discovery.is_some() => {
+                log::info!("No applicable plugin could be found, only the coordinate discovery tool will be available")
+            }
+            (None, None) => eyre::bail!("No applicable plugin could be found, disabling SkipTheRunback"),
+        }
+
+        let discovery = CoordinateDiscovery::new(
+            data_directory.clone(),
+            &config.discovery.clone().unwrap_or_default(),
+            platform.clone(),
+        );
+
+        let mut runner = Self {
+            app,
+            deferred,
+            discovery,
+            watcher: None,
+            config_directory,
+            data_directory,
+            platform,
+        };
+        runner.update_watcher(config);
+
+        Ok(runner)
+    }
+
+    /// Start or stop watching the config files, depending on [SkipConfig::hot_reload].
+    fn update_watcher(&mut self, config: &SkipConfig) {
+        if !config.hot_reload {
+            self.watcher = None;
+        } else if self.watcher.is_none() {
+            match FileWatcher::new(&self.config_directory, &self.data_directory) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(e) => log::warn!("Failed to watch the config files, they'll only be reloaded by key press: {e:#}"),
+            }
+        }
+    }
+
+    /// Reload everything which changed, the config files listed in `changes` are reloaded by the `host`.
+    fn reload(&mut self, config: &mut SkipConfig, changes: watcher::FileChanges, host: &mut dyn Host) {
+        if changes.config {
+            match host.reload_config(config) {
+                Ok(new_config) => {
+                    let changed = config::diff(config, &new_config);
+                    if new_config.data_directory != config.data_directory {
+                        log::warn!("The new `data_directory` is only used after restarting the game");
+                    }
+                    if changed.is_empty() {
+                        log::info!("Reloaded the config, nothing changed");
+                    } else {
+                        log::info!("Reloaded the config, changed settings:\n  {}", changed.join("\n  "));
+                    }
+
+                    *config = new_config;
+                    self.update_watcher(config);
+                }
+                Err(e) => log::error!("Failed to reload the config, keeping the previous one: {e:#}"),
+            }
+        }
+
+        if let Some(app) = &mut self.app {
+            if changes.plugins {
+                app.reload_plugin_config();
+            }
+            if changes.waypoints {
+                app.reload_waypoints();
+            }
+        }
+    }
+
+    /// Retry starting a plugin if a deferred start is pending and the next attempt is due.
+    ///
+    /// # Errors
+    ///
+    /// If the deferred start timed out while the discovery tool is disabled.
+    fn poll_deferred_start(&mut self, config: &SkipConfig) -> eyre::Result<()> {
+        let Some(deferred) = &mut self.deferred else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        if now < deferred.next_attempt {
+            return Ok(());
+        }
+
+        match plugins::start_plugin(&mut deferred.plugins, &config.plugins, &*self.platform, false) {
+            Ok(Some(plugin)) => {
+                self.deferred = None;
+                self.app = Some(SkipApp::new(&self.data_directory, plugin, self.platform.clone())?);
+            }
+            Ok(None) if now < deferred.deadline => {
+                deferred.interval = (deferred.interval * 2).min(deferred.max_interval);
+                deferred.next_attempt = now + deferred.interval;
+            }
+            result => {
+                self.deferred = None;
+                let reason = match result {
+                    Err(e) => format!("{e:#}"),
+                    _ => "No applicable plugin could be found".to_string(),
+                };
+
+                if config.discovery.is_some() {
+                    log::warn!(
+                        "Gave up on starting a plugin, only the coordinate discovery tool will be available. {reason}"
+                    );
+                } else {
+                    eyre::bail!("Gave up on starting a plugin, disabling SkipTheRunback. {reason}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a single frame.
+    pub fn run(&mut self, config: &SkipConfig, keyboard: &mut dyn KeyInput) -> eyre::Result<()> {
+        if let Some(app) = &mut self.app {
+            app.run(config, keyboard)?;
+        }
+        if let Some(discovery_conf) = &config.discovery {
+            self.discovery.run(discovery_conf, keyboard)?;
+        }
+
+        Ok(())
+    }
+
+    /// Run at 60 frames per second until `shutdown` is set.
+    pub fn run_until(
+        &mut self,
+        mut config: SkipConfig,
+        keyboard: &mut dyn KeyInput,
+        host: &mut dyn Host,
+        shutdown: &AtomicBool,
+    ) -> eyre::Result<()> {
+        let update_duration = Duration::from_secs_f64(1.0 / 60.);
+
+        while !shutdown.load(Ordering::Acquire) {
+            let mut changes = self.watcher.as_mut().map(FileWatcher::poll).unwrap_or_default();
+            let reload_pressed = config
+                .reload_config_keys
+                .as_ref()
+                .is_some_and(|reload| keyboard.all_pressed(reload));
+            // A `reload` key binding from the previous frame.
+            let reload_requested = self.app.as_mut().is_some_and(SkipApp::take_reload_request);
+            if reload_pressed || reload_requested {
+                changes = watcher::FileChanges {
+                    config: true,
+                    plugins: true,
+                    waypoints: changes.waypoints,
+                };
+            }
+            self.reload(&mut config, changes, host);
+
+            self.poll_deferred_start(&config)?;
+
+            // Only run if we're in the foreground. A bit hacky, but eh...
+            if host.is_foreground() {
+                self.run(&config, keyboard)?;
+            }
+
+            std::thread::sleep(update_duration);
+            keyboard.end_frame();
+        }
+
+        Ok(())
+    }
+}
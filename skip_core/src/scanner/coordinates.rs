@@ -0,0 +1,456 @@
+//! Cheat Engine style search for `x/y/z` float triplets.
+//!
+//! The usual flow is: take a [CoordinateScanner::snapshot], move the player and [narrow](CoordinateScanner::narrow)
+//! with [Movement::Moved], stand still and narrow with [Movement::Stationary], and repeat until only a handful of
+//! candidates remain.
+
+use std::time::Instant;
+
+use crate::scanner::{MemoryRegion, MemorySource};
+
+const TRIPLET_SIZE: usize = 3 * std::mem::size_of::<f32>();
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct ScanSettings {
+    /// The alignment (in bytes) of the first float in a triplet.
+    pub alignment: usize,
+    /// Any coordinate with an absolute value above this is considered garbage.
+    pub max_abs_value: f32,
+    /// The minimum distance a triplet has to have moved to be considered as [Movement::Moved].
+    pub min_move_distance: f32,
+    /// The maximum distance a triplet may have moved to be considered as [Movement::Moved], filters out random data.
+    pub max_move_distance: f32,
+    /// The maximum distance a triplet may have moved while still being considered as [Movement::Stationary].
+    pub stationary_epsilon: f32,
+    /// The maximum amount of bytes captured by a snapshot over all regions, or `None` for no limit. Regions which
+    /// would exceed it are skipped.
+    #[serde(default = "default_max_snapshot_size")]
+    pub max_snapshot_size: Option<usize>,
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            alignment: 4,
+            max_abs_value: 1_000_000.0,
+            min_move_distance: 0.01,
+            max_move_distance: 1_000.0,
+            stationary_epsilon: 0.0001,
+            max_snapshot_size: default_max_snapshot_size(),
+        }
+    }
+}
+
+fn default_max_snapshot_size() -> Option<usize> {
+    Some(1024 * 1024 * 1024)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// The player has moved since the last snapshot/narrowing.
+    Moved,
+    /// The player has stood still since the last snapshot/narrowing.
+    Stationary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Candidate {
+    /// Address of the first float of the triplet.
+    pub address: usize,
+    /// The value of the triplet as of the last narrowing.
+    pub value: [f32; 3],
+}
+
+#[derive(Debug, Default)]
+enum ScanState {
+    #[default]
+    Empty,
+    /// Raw copy of all regions, before we've done any narrowing.
+    Snapshot(Vec<RegionSnapshot>),
+    /// Candidates sorted by address.
+    Candidates(Vec<Candidate>),
+}
+
+#[derive(Debug)]
+struct RegionSnapshot {
+    region: MemoryRegion,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct CoordinateScanner {
+    settings: ScanSettings,
+    state: ScanState,
+}
+
+impl CoordinateScanner {
+    pub fn new(settings: ScanSettings) -> Self {
+        Self {
+            settings,
+            state: ScanState::Empty,
+        }
+    }
+
+    /// Whether a scan is currently in progress.
+    pub fn is_active(&self) -> bool {
+        !matches!(self.state, ScanState::Empty)
+    }
+
+    /// The remaining candidates, empty if we haven't narrowed down a snapshot yet.
+    pub fn candidates(&self) -> &[Candidate] {
+        match &self.state {
+            ScanState::Candidates(candidates) => candidates,
+            _ => &[],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = ScanState::Empty;
+    }
+
+    /// Start a new scan by copying all readable memory in `source`, up to [ScanSettings::max_snapshot_size] bytes.
+    ///
+    /// Returns the amount of bytes which were captured.
+    pub fn snapshot(&mut self, source: &dyn MemorySource) -> usize {
+        let now = Instant::now();
+        let budget = self.settings.max_snapshot_size.unwrap_or(usize::MAX);
+        let mut total = 0;
+        let mut skipped = 0;
+        let mut snapshots = Vec::new();
+
+        for region in source.regions() {
+            if region.size > budget - total {
+                skipped += 1;
+                continue;
+            }
+
+            let mut data = vec![0; region.size];
+            if source.read(region.base, &mut data).is_ok() {
+                total += data.len();
+                snapshots.push(RegionSnapshot { region, data });
+            }
+        }
+
+        if skipped > 0 {
+            log::warn!(
+                "Skipped {skipped} regions as the snapshot would exceed {budget} bytes, see `max_snapshot_size`"
+            );
+        }
+        log::debug!(
+            "Took snapshot of {} regions ({total} bytes) in {:?}",
+            snapshots.len(),
+            now.elapsed()
+        );
+        self.state = ScanState::Snapshot(snapshots);
+
+        total
+    }
+
+    /// Only keep the triplets which changed according to `movement` since the last snapshot/narrowing.
+    ///
+    /// Returns the amount of remaining candidates.
+    ///
+    /// # Errors
+    ///
+    /// If no snapshot was taken yet.
+    pub fn narrow(&mut self, source: &dyn MemorySource, movement: Movement) -> eyre::Result<usize> {
+        let now = Instant::now();
+        let candidates = match std::mem::take(&mut self.state) {
+            ScanState::Empty => eyre::bail!("No snapshot was taken yet, can't narrow down the search"),
+            ScanState::Snapshot(snapshots) => self.narrow_snapshots(source, snapshots, movement),
+            ScanState::Candidates(candidates) => self.narrow_candidates(source, candidates, movement),
+        };
+        let remaining = candidates.len();
+
+        log::debug!("Narrowed down to {remaining} candidates in {:?}", now.elapsed());
+        self.state = ScanState::Candidates(candidates);
+
+        Ok(remaining)
+    }
+
+    fn narrow_snapshots(
+        &self,
+        source: &dyn MemorySource,
+        snapshots: Vec<RegionSnapshot>,
+        movement: Movement,
+    ) -> Vec<Candidate> {
+        let mut output = Vec::new();
+        let mut current = Vec::new();
+
+        for snapshot in snapshots {
+            current.resize(snapshot.data.len(), 0);
+            if source.read(snapshot.region.base, &mut current).is_err() {
+                // Region was most likely freed in the meantime.
+                continue;
+            }
+
+            let alignment = self.settings.alignment.max(1);
+            let mut offset = align_up(snapshot.region.base, alignment) - snapshot.region.base;
+            while offset + TRIPLET_SIZE <= current.len() {
+                let old = read_triplet(&snapshot.data[offset..]);
+                let new = read_triplet(&current[offset..]);
+
+                if self.matches(old, new, movement) {
+                    output.push(Candidate {
+                        address: snapshot.region.base + offset,
+                        value: new,
+                    });
+                }
+
+                offset += alignment;
+            }
+        }
+
+        output
+    }
+
+    fn narrow_candidates(
+        &self,
+        source: &dyn MemorySource,
+        candidates: Vec<Candidate>,
+        movement: Movement,
+    ) -> Vec<Candidate> {
+        let mut output = Vec::with_capacity(candidates.len());
+        let mut remaining = candidates.as_slice();
+        let mut current = Vec::new();
+
+        // Read entire regions at once, as reading every candidate individually is a lot slower.
+        for region in source.regions() {
+            let start = remaining.partition_point(|c| c.address < region.base);
+            remaining = &remaining[start..];
+            let end = remaining.partition_point(|c| c.address + TRIPLET_SIZE <= region.end());
+            let (in_region, rest) = remaining.split_at(end);
+            remaining = rest;
+
+            if in_region.is_empty() {
+                continue;
+            }
+
+            current.resize(region.size, 0);
+            if source.read(region.base, &mut current).is_err() {
+                continue;
+            }
+
+            output.extend(in_region.iter().filter_map(|candidate| {
+                let new = read_triplet(&current[candidate.address - region.base..]);
+
+                self.matches(candidate.value, new, movement).then_some(Candidate {
+                    address: candidate.address,
+                    value: new,
+                })
+            }));
+        }
+
+        output
+    }
+
+    fn matches(&self, old: [f32; 3], new: [f32; 3], movement: Movement) -> bool {
+        if !self.is_plausible(old) || !self.is_plausible(new) {
+            return false;
+        }
+
+        let distance = old
+            .iter()
+            .zip(new)
+            .map(|(old, new)| (new - old) * (new - old))
+            .sum::<f32>()
+            .sqrt();
+
+        match movement {
+            Movement::Moved => {
+                distance >= self.settings.min_move_distance && distance <= self.settings.max_move_distance
+            }
+            Movement::Stationary => distance <= self.settings.stationary_epsilon,
+        }
+    }
+
+    fn is_plausible(&self, triplet: [f32; 3]) -> bool {
+        // All zero triplets are far too common to be useful.
+        triplet.iter().any(|v| *v != 0.0)
+            && triplet
+                .iter()
+                .all(|v| (v.is_normal() || *v == 0.0) && v.abs() <= self.settings.max_abs_value)
+    }
+}
+
+fn read_triplet(data: &[u8]) -> [f32; 3] {
+    let read = |i: usize| f32::from_ne_bytes(data[i * 4..i * 4 + 4].try_into().unwrap());
+    [read(0), read(1), read(2)]
+}
+
+fn align_up(value: usize, alignment: usize) -> usize {
+    value.div_ceil(alignment) * alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryAccess, MockMemory};
+
+    const REGION: usize = 0x1000;
+    const PLAYER: usize = REGION + 0x40;
+
+    /// A region filled with NaNs, which are never plausible coordinates, containing the player at [PLAYER].
+    fn memory(size: usize) -> MockMemory {
+        let memory = MockMemory::new();
+        memory.map_bytes(REGION, f32::NAN.to_ne_bytes().repeat(size / 4), true);
+        write(&memory, PLAYER, [1.0, 2.0, 3.0]);
+        memory
+    }
+
+    fn write(memory: &MockMemory, address: usize, value: [f32; 3]) {
+        for (i, v) in value.into_iter().enumerate() {
+            memory.write_f32(address + i * 4, v).unwrap();
+        }
+    }
+
+    fn addresses(scanner: &CoordinateScanner) -> Vec<usize> {
+        scanner.candidates().iter().map(|c| c.address).collect()
+    }
+
+    #[test]
+    fn narrow_requires_snapshot() {
+        let memory = memory(0x100);
+        let mut scanner = CoordinateScanner::default();
+
+        assert!(scanner.narrow(&memory, Movement::Moved).is_err());
+        assert!(!scanner.is_active());
+    }
+
+    #[test]
+    fn moved_and_stationary() {
+        let memory = memory(0x100);
+        // Stays put the entire time, so it has to be removed by the first `Moved`.
+        write(&memory, REGION + 0x80, [5.0, 6.0, 7.0]);
+        let mut scanner = CoordinateScanner::new(ScanSettings::default());
+
+        assert_eq!(scanner.snapshot(&memory), 0x100);
+        write(&memory, PLAYER, [1.5, 2.0, 3.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 1);
+        assert_eq!(addresses(&scanner), [PLAYER]);
+        assert_eq!(scanner.candidates()[0].value, [1.5, 2.0, 3.0]);
+
+        assert_eq!(scanner.narrow(&memory, Movement::Stationary).unwrap(), 1);
+        write(&memory, PLAYER, [1.5, 2.0, 4.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Stationary).unwrap(), 0);
+    }
+
+    #[test]
+    fn stationary_from_snapshot() {
+        let memory = memory(0x100);
+        write(&memory, REGION + 0x80, [5.0, 6.0, 7.0]);
+        let mut scanner = CoordinateScanner::default();
+
+        scanner.snapshot(&memory);
+        write(&memory, REGION + 0x80, [5.0, 6.0, 8.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Stationary).unwrap(), 1);
+        assert_eq!(addresses(&scanner), [PLAYER]);
+    }
+
+    #[test]
+    fn float_tolerance() {
+        let memory = memory(0x100);
+        let mut scanner = CoordinateScanner::new(ScanSettings {
+            min_move_distance: 0.1,
+            max_move_distance: 10.0,
+            stationary_epsilon: 0.01,
+            ..Default::default()
+        });
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [1.05, 2.0, 3.0]);
+        assert_eq!(
+            scanner.narrow(&memory, Movement::Moved).unwrap(),
+            0,
+            "moved less than the minimum"
+        );
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [21.0, 2.0, 3.0]);
+        assert_eq!(
+            scanner.narrow(&memory, Movement::Moved).unwrap(),
+            0,
+            "moved more than the maximum"
+        );
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [22.0, 2.0, 3.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 1);
+
+        write(&memory, PLAYER, [22.005, 2.0, 3.0]);
+        assert_eq!(
+            scanner.narrow(&memory, Movement::Stationary).unwrap(),
+            1,
+            "within the epsilon"
+        );
+        write(&memory, PLAYER, [22.05, 2.0, 3.0]);
+        assert_eq!(
+            scanner.narrow(&memory, Movement::Stationary).unwrap(),
+            0,
+            "outside of the epsilon"
+        );
+    }
+
+    #[test]
+    fn implausible_values() {
+        let memory = memory(0x100);
+        let mut scanner = CoordinateScanner::new(ScanSettings {
+            max_abs_value: 100.0,
+            ..Default::default()
+        });
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [1.0, 2.0, 300.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 0);
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [1.0, 2.0, f32::INFINITY]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 0);
+    }
+
+    #[test]
+    fn region_boundaries() {
+        let memory = memory(0x100);
+        let last = REGION + 0x100 - TRIPLET_SIZE;
+        write(&memory, PLAYER, [f32::NAN; 3]);
+        write(&memory, last, [1.0, 2.0, 3.0]);
+        // The triplets spanning both regions would match as well if they were considered.
+        memory.map(REGION + 0x100, 0x100, true);
+        let mut scanner = CoordinateScanner::default();
+
+        scanner.snapshot(&memory);
+        write(&memory, last, [1.0, 2.0, 4.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 1);
+        assert_eq!(addresses(&scanner), [last]);
+    }
+
+    #[test]
+    fn freed_region() {
+        let memory = memory(0x100);
+        let mut scanner = CoordinateScanner::default();
+
+        scanner.snapshot(&memory);
+        write(&memory, PLAYER, [1.5, 2.0, 3.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 1);
+
+        memory.unmap(REGION);
+        assert_eq!(scanner.narrow(&memory, Movement::Stationary).unwrap(), 0);
+    }
+
+    #[test]
+    fn snapshot_budget() {
+        let memory = memory(0x100);
+        memory.map(0x10_000, 0x200, true);
+        memory.map(0x20_000, 0x80, true);
+        let mut scanner = CoordinateScanner::new(ScanSettings {
+            max_snapshot_size: Some(0x180),
+            ..Default::default()
+        });
+
+        // The second region doesn't fit anymore, but the third one still does.
+        assert_eq!(scanner.snapshot(&memory), 0x180);
+
+        write(&memory, PLAYER, [1.5, 2.0, 3.0]);
+        assert_eq!(scanner.narrow(&memory, Movement::Moved).unwrap(), 1);
+    }
+}
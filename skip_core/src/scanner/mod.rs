@@ -0,0 +1,84 @@
+//! Memory scanning utilities used to discover player coordinates (and static pointers to them) in games which don't
+//! have a (complete) plugin yet.
+//!
+//! The search logic only ever talks to a [MemorySource], so the same code runs against the live process or any other
+//! (synthetic) address space.
+
+use std::sync::Arc;
+
+pub mod coordinates;
+pub mod pointers;
+
+/// A contiguous range of memory which can be scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemoryRegion {
+    pub base: usize,
+    pub size: usize,
+}
+
+impl MemoryRegion {
+    pub fn end(&self) -> usize {
+        self.base + self.size
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        (self.base..self.end()).contains(&address)
+    }
+}
+
+/// Which parts of the address space a [MemorySource] should consider.
+///
+/// By default only private, committed, and writable memory is considered, as that's where the game's heap (and thus
+/// the player struct) will reside.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// Regions larger than this are skipped, to avoid snapshotting gigantic (GPU upload) buffers.
+    pub max_region_size: Option<usize>,
+    /// Whether to include the writable sections of loaded modules, required for finding static pointers.
+    pub include_modules: bool,
+}
+
+/// A module (executable/DLL) loaded in the address space, used as the static base of pointer chains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub base: usize,
+    pub size: usize,
+}
+
+impl Module {
+    pub fn contains(&self, address: usize) -> bool {
+        (self.base..self.base + self.size).contains(&address)
+    }
+}
+
+pub trait MemorySource {
+    /// All regions which should be considered during a scan, sorted by their base address.
+    fn regions(&self) -> Vec<MemoryRegion>;
+
+    /// Read `buf.len()` bytes starting at `address` into `buf`.
+    ///
+    /// # Errors
+    ///
+    /// If (part of) the given range could not be read.
+    fn read(&self, address: usize, buf: &mut [u8]) -> eyre::Result<()>;
+
+    /// All modules loaded in this address space.
+    fn modules(&self) -> Vec<Module> {
+        Vec::new()
+    }
+}
+
+impl<T: MemorySource + ?Sized> MemorySource for Arc<T> {
+    fn regions(&self) -> Vec<MemoryRegion> {
+        (**self).regions()
+    }
+
+    fn read(&self, address: usize, buf: &mut [u8]) -> eyre::Result<()> {
+        (**self).read(address, buf)
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        (**self).modules()
+    }
+}
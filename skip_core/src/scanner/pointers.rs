@@ -0,0 +1,272 @@
+//! Backwards pointer scan, finding static `module+offset -> offset -> ...` chains which lead to a given address.
+//!
+//! Starting from the target we look for every pointer which points at most [PointerScanSettings::max_offset] bytes
+//! before it. Pointers residing inside a module form a complete chain, the others become the targets of the next level,
+//! until [PointerScanSettings::max_depth] is reached.
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+use crate::plugins::generic::{PointerChain, RelativePointer};
+use crate::scanner::{MemoryRegion, MemorySource};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct PointerScanSettings {
+    /// Maximum amount of dereferences in a chain.
+    pub max_depth: usize,
+    /// Maximum offset which may be added after a dereference.
+    pub max_offset: usize,
+    /// Maximum amount of chains to return.
+    pub max_results: usize,
+    /// Maximum amount of intermediate addresses to follow per level, bounds the time a scan can take.
+    pub max_nodes_per_level: usize,
+}
+
+impl Default for PointerScanSettings {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_offset: 0x1000,
+            max_results: 20,
+            max_nodes_per_level: 50_000,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PointerScanner {
+    settings: PointerScanSettings,
+}
+
+struct Node {
+    address: usize,
+    /// The offsets which lead from the value stored at `address` to the target.
+    offsets: Vec<isize>,
+}
+
+impl PointerScanner {
+    pub fn new(settings: PointerScanSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Find static chains leading to `target`, ranked from shortest to longest (and smallest to largest offsets).
+    pub fn scan(&self, source: &dyn MemorySource, target: usize) -> Vec<PointerChain> {
+        let now = Instant::now();
+        let modules = source.modules();
+        let pointers = PointerMap::build(source, &source.regions());
+        log::debug!("Built pointer map of {} entries in {:?}", pointers.len(), now.elapsed());
+
+        let mut results = Vec::new();
+        let mut visited = HashSet::from([target]);
+        let mut level = vec![Node {
+            address: target,
+            offsets: Vec::new(),
+        }];
+
+        for depth in 0..self.settings.max_depth {
+            let mut next_level = Vec::new();
+
+            for node in &level {
+                let lowest = node.address.saturating_sub(self.settings.max_offset);
+
+                for &(value, location) in pointers.pointing_into(lowest, node.address) {
+                    let mut offsets = Vec::with_capacity(node.offsets.len() + 1);
+                    offsets.push((node.address - value) as isize);
+                    offsets.extend_from_slice(&node.offsets);
+
+                    if let Some(module) = modules.iter().find(|m| m.contains(location)) {
+                        results.push(PointerChain {
+                            base: RelativePointer::new(&module.name, location - module.base),
+                            offsets,
+                        });
+                    } else if next_level.len() < self.settings.max_nodes_per_level && visited.insert(location) {
+                        next_level.push(Node {
+                            address: location,
+                            offsets,
+                        });
+                    }
+                }
+            }
+
+            log::debug!(
+                "Pointer scan depth {}: {} chains found, {} addresses to follow",
+                depth + 1,
+                results.len(),
+                next_level.len()
+            );
+
+            // Deeper levels can only produce worse chains.
+            if results.len() >= self.settings.max_results || next_level.is_empty() {
+                break;
+            }
+
+            level = next_level;
+        }
+
+        results.sort_by_key(|chain| {
+            let total_offset: usize = chain.offsets.iter().map(|o| o.unsigned_abs()).sum();
+            (chain.offsets.len(), total_offset)
+        });
+        results.truncate(self.settings.max_results);
+
+        log::debug!("Pointer scan finished in {:?}", now.elapsed());
+
+        results
+    }
+}
+
+/// All pointer sized values in the scanned regions which point into one of said regions, sorted by value.
+struct PointerMap {
+    /// `(value, location)` pairs.
+    entries: Vec<(usize, usize)>,
+}
+
+impl PointerMap {
+    fn build(source: &dyn MemorySource, regions: &[MemoryRegion]) -> Self {
+        const PTR_SIZE: usize = std::mem::size_of::<usize>();
+        let mut entries = Vec::new();
+        let mut data = Vec::new();
+
+        let points_into_region = |value: usize| {
+            let idx = regions.partition_point(|r| r.end() <= value);
+            regions.get(idx).map(|r| r.contains(value)).unwrap_or_default()
+        };
+
+        for region in regions {
+            data.resize(region.size, 0);
+            if source.read(region.base, &mut data).is_err() {
+                continue;
+            }
+
+            entries.extend(
+                data.chunks_exact(PTR_SIZE)
+                    .enumerate()
+                    .map(|(i, bytes)| {
+                        let value = usize::from_ne_bytes(bytes.try_into().unwrap());
+                        (value, region.base + i * PTR_SIZE)
+                    })
+                    .filter(|(value, _)| points_into_region(*value)),
+            );
+        }
+
+        entries.sort_unstable();
+
+        Self { entries }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// All entries whose value lies in `[low, high]`.
+    fn pointing_into(&self, low: usize, high: usize) -> &[(usize, usize)] {
+        let start = self.entries.partition_point(|(value, _)| *value < low);
+        let end = self.entries.partition_point(|(value, _)| *value <= high);
+        &self.entries[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryAccess, MockMemory};
+
+    const MODULE: usize = 0x10_000;
+    const HEAP: usize = 0x40_000;
+    const PLAYER: usize = 0x50_000;
+    const TARGET: usize = PLAYER + 0x80;
+
+    /// `game.exe+20 -> 10 -> 80`, where `game.exe+20` points to a heap object whose field at `+10` points to the player.
+    fn memory() -> MockMemory {
+        let mut memory = MockMemory::new();
+        memory.add_module("game.exe", MODULE, 0x1000);
+        memory.map(MODULE, 0x1000, true);
+        memory.map(HEAP, 0x1000, true);
+        memory.map(PLAYER, 0x1000, true);
+
+        memory.write_bytes(MODULE + 0x20, &HEAP.to_ne_bytes()).unwrap();
+        memory.write_bytes(HEAP + 0x10, &PLAYER.to_ne_bytes()).unwrap();
+        memory
+    }
+
+    fn chain(offset: usize, offsets: &[isize]) -> PointerChain {
+        PointerChain {
+            base: RelativePointer::new("game.exe", offset),
+            offsets: offsets.to_vec(),
+        }
+    }
+
+    #[test]
+    fn multi_level_chain() {
+        let memory = memory();
+        let chains = PointerScanner::default().scan(&memory, TARGET);
+
+        assert_eq!(chains, [chain(0x20, &[0x10, 0x80])]);
+        assert_eq!(chains[0].follow(MODULE + 0x20, &memory).unwrap(), TARGET);
+    }
+
+    #[test]
+    fn ranking() {
+        let memory = memory();
+        // A direct static pointer to the player, and one further away from it.
+        memory
+            .write_bytes(MODULE + 0x100, &(TARGET - 0x8).to_ne_bytes())
+            .unwrap();
+        memory.write_bytes(MODULE + 0x200, &PLAYER.to_ne_bytes()).unwrap();
+
+        let chains = PointerScanner::default().scan(&memory, TARGET);
+        assert_eq!(
+            chains,
+            [chain(0x100, &[0x8]), chain(0x200, &[0x80]), chain(0x20, &[0x10, 0x80])]
+        );
+
+        let chains = PointerScanner::new(PointerScanSettings {
+            max_results: 1,
+            ..Default::default()
+        })
+        .scan(&memory, TARGET);
+        assert_eq!(chains, [chain(0x100, &[0x8])]);
+    }
+
+    #[test]
+    fn depth_limit() {
+        let memory = memory();
+        let scanner = PointerScanner::new(PointerScanSettings {
+            max_depth: 1,
+            ..Default::default()
+        });
+
+        assert!(scanner.scan(&memory, TARGET).is_empty());
+    }
+
+    #[test]
+    fn offset_limit() {
+        let memory = memory();
+        let scanner = PointerScanner::new(PointerScanSettings {
+            max_offset: 0x40,
+            ..Default::default()
+        });
+        assert!(scanner.scan(&memory, TARGET).is_empty());
+
+        // The offsets are inclusive.
+        let scanner = PointerScanner::new(PointerScanSettings {
+            max_offset: 0x80,
+            ..Default::default()
+        });
+        assert_eq!(scanner.scan(&memory, TARGET), [chain(0x20, &[0x10, 0x80])]);
+    }
+
+    #[test]
+    fn cycles() {
+        let memory = memory();
+        // The player points back to the heap object, which must not be followed in circles.
+        memory.write_bytes(PLAYER, &HEAP.to_ne_bytes()).unwrap();
+        memory.write_bytes(HEAP, &PLAYER.to_ne_bytes()).unwrap();
+
+        let chains = PointerScanner::default().scan(&memory, TARGET);
+        assert!(chains.contains(&chain(0x20, &[0x10, 0x80])));
+        assert!(chains
+            .iter()
+            .all(|chain| chain.follow(MODULE + 0x20, &memory).unwrap() == TARGET));
+    }
+}
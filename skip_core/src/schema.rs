@@ -0,0 +1,168 @@
+//! JSON Schemas of all files a user might edit by hand, for auto-completion and validation in editors.
+
+use std::path::Path;
+
+use schemars::schema::{RootSchema, SchemaObject};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::SkipConfig;
+use crate::plugins::generic::GenericConfig;
+use crate::waypoints::WaypointSave;
+
+/// Directory (relative to the config directory) in which the schemas are written.
+pub static SCHEMA_DIRECTORY_NAME: &str = "schemas";
+
+/// A file format with a schema.
+pub trait SchemaFile: JsonSchema {
+    /// File name of the schema in the [SCHEMA_DIRECTORY_NAME] directory.
+    const SCHEMA_FILE_NAME: &'static str;
+
+    fn schema() -> RootSchema {
+        schemars::schema_for!(Self)
+    }
+}
+
+impl SchemaFile for SkipConfig {
+    const SCHEMA_FILE_NAME: &'static str = "skip_rs_config.schema.json";
+}
+
+impl SchemaFile for GenericConfig {
+    const SCHEMA_FILE_NAME: &'static str = "plugin.schema.json";
+
+    /// Configs which extend a built-in plugin only contain the top-level fields they override, so those aren't required.
+    fn schema() -> RootSchema {
+        let mut root = schemars::schema_for!(Self);
+        let object = root.schema.object();
+        let required = std::mem::take(&mut object.required);
+
+        let mut extends = SchemaObject::default();
+        extends.object().required.insert("extends".into());
+        let mut full = SchemaObject::default();
+        full.object().required = required;
+
+        root.schema.subschemas().if_schema = Some(Box::new(extends.into()));
+        root.schema.subschemas().else_schema = Some(Box::new(full.into()));
+        root
+    }
+}
+
+impl SchemaFile for WaypointSave {
+    const SCHEMA_FILE_NAME: &'static str = "waypoints.schema.json";
+}
+
+/// The schema of `T` as pretty printed JSON.
+///
+/// # Example
+/// ```rust
+/// # use skip_core::plugins::generic::GenericConfig;
+/// # use skip_core::schema::schema_json;
+/// let schema = schema_json::<GenericConfig>().unwrap();
+/// assert!(schema.contains("\"pointer_offsets\""));
+/// ```
+pub fn schema_json<T: SchemaFile>() -> eyre::Result<String> {
+    Ok(serde_json::to_string_pretty(&T::schema())?)
+}
+
+/// Write the schemas of all file formats to the [SCHEMA_DIRECTORY_NAME] directory in `directory`.
+///
+/// Existing schemas are overwritten, so they always match the running version.
+pub fn write_schemas(directory: &Path) -> eyre::Result<()> {
+    let schema_dir = directory.join(SCHEMA_DIRECTORY_NAME);
+    std::fs::create_dir_all(&schema_dir)?;
+
+    for (file_name, schema) in [
+        (SkipConfig::SCHEMA_FILE_NAME, schema_json::<SkipConfig>()?),
+        (GenericConfig::SCHEMA_FILE_NAME, schema_json::<GenericConfig>()?),
+        (WaypointSave::SCHEMA_FILE_NAME, schema_json::<WaypointSave>()?),
+    ] {
+        std::fs::write(schema_dir.join(file_name), schema)?;
+    }
+
+    Ok(())
+}
+
+/// Serialize `value` as pretty printed JSON with a `$schema` reference, for a file stored in the config directory.
+pub fn to_json_with_schema<T: SchemaFile + Serialize>(value: &T) -> eyre::Result<String> {
+    to_nested_json_with_schema(value, 0)
+}
+
+/// Like [to_json_with_schema], for a file stored `depth` directories below the directory containing the schemas, such as
+/// the waypoints of a profile.
+pub fn to_nested_json_with_schema<T: SchemaFile + Serialize>(value: &T, depth: usize) -> eyre::Result<String> {
+    let mut json = serde_json::to_value(value)?;
+    if let Value::Object(fields) = &mut json {
+        let parent = if depth == 0 { "./".to_string() } else { "../".repeat(depth) };
+        let reference = format!("{parent}{SCHEMA_DIRECTORY_NAME}/{}", T::SCHEMA_FILE_NAME);
+        fields.insert("$schema".into(), reference.into());
+    }
+
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Format;
+    use crate::waypoints::Waypoint;
+
+    fn validator<T: SchemaFile>() -> jsonschema::JSONSchema {
+        let schema = serde_json::from_str(&schema_json::<T>().unwrap()).unwrap();
+        jsonschema::JSONSchema::compile(&schema).unwrap()
+    }
+
+    #[test]
+    fn plugin_schema() {
+        let plugin = validator::<GenericConfig>();
+        let example = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../example_configs/skip_runback_plugin_SEKIRO.json"
+        ));
+        assert!(plugin.is_valid(&serde_json::from_str(example).unwrap()));
+        let example = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../example_configs/skip_runback_plugin_SEKIRO.toml"
+        ));
+        assert!(plugin.is_valid(&Format::Toml.parse_value(example.as_bytes()).unwrap()));
+        for builtin in crate::plugins::builtin_configs() {
+            assert!(plugin.is_valid(&serde_json::to_value(builtin).unwrap()));
+        }
+
+        assert!(!plugin.is_valid(&serde_json::json!({ "position": { "InterceptPointer": {} } })));
+    }
+
+    #[test]
+    fn extending_plugin_schema() {
+        let plugin = validator::<GenericConfig>();
+        let offsets = serde_json::json!({ "x": "0xF0", "y": "0xF4", "z": "0xF8" });
+        assert!(plugin.is_valid(&serde_json::json!({ "extends": "Nioh 2", "pointer_offsets": offsets })));
+        assert!(!plugin.is_valid(&serde_json::json!({ "pointer_offsets": offsets })));
+    }
+
+    #[test]
+    fn config_schema() {
+        let config = validator::<SkipConfig>();
+        assert!(config.is_valid(&serde_json::to_value(SkipConfig::default()).unwrap()));
+        assert!(!config.is_valid(&serde_json::json!({ "console": "yes" })));
+    }
+
+    #[test]
+    fn waypoint_schema() {
+        let waypoints = validator::<WaypointSave>();
+        let mut save = WaypointSave::default();
+        save.slots.insert(1, Waypoint { x: 1.0, y: 2.0, z: 3.0 });
+        assert!(waypoints.is_valid(&serde_json::to_value(save).unwrap()));
+    }
+
+    #[test]
+    fn schema_reference() {
+        let json: Value = serde_json::from_str(&to_json_with_schema(&SkipConfig::default()).unwrap()).unwrap();
+        assert_eq!(json["$schema"], "./schemas/skip_rs_config.schema.json");
+        assert!(validator::<SkipConfig>().is_valid(&json));
+
+        let json: Value =
+            serde_json::from_str(&to_nested_json_with_schema(&WaypointSave::default(), 2).unwrap()).unwrap();
+        assert_eq!(json["$schema"], "../../schemas/waypoints.schema.json");
+    }
+}
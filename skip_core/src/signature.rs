@@ -0,0 +1,32 @@
+//! Byte signatures in the common `48 8B ?? 05` IDA style, where `?` or `??` matches any byte.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature(Vec<Option<u8>>);
+
+impl Signature {
+    pub fn parse(signature: &str) -> eyre::Result<Self> {
+        let bytes = signature
+            .split_whitespace()
+            .map(|byte| match byte {
+                "?" | "??" => Ok(None),
+                _ => u8::from_str_radix(byte, 16)
+                    .map(Some)
+                    .map_err(|e| eyre::eyre!("Invalid byte `{byte}` in signature `{signature}`: {e}")),
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        eyre::ensure!(!bytes.is_empty(), "Empty signature");
+
+        Ok(Self(bytes))
+    }
+
+    /// Find the offset of the first match of this signature in `haystack`.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        haystack.windows(self.0.len()).position(|window| {
+            window
+                .iter()
+                .zip(&self.0)
+                .all(|(byte, expected)| expected.map(|expected| expected == *byte).unwrap_or(true))
+        })
+    }
+}
@@ -0,0 +1,222 @@
+//! Watching the config directory, so that changes to the config, plugin configs and waypoints are picked up without
+//! pressing the reload keys.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::app::{PROFILE_DIRECTORY_NAME, WAYPOINTS_FILE_NAME};
+use crate::config::CONFIG_FILE_STEM;
+use crate::format::Format;
+use crate::plugins::generic::{PLUGIN_DIRECTORY_NAME, SKIP_PLUGIN_FILENAME};
+
+/// Changes are only reported once no further events came in for this long, as editors often write a file in several
+/// steps (or save it multiple times in a row).
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The kinds of files which changed since the last reported change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileChanges {
+    pub config: bool,
+    pub plugins: bool,
+    pub waypoints: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchedFile {
+    Config,
+    Plugin,
+    Waypoints,
+}
+
+pub struct FileWatcher {
+    watcher: notify::RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    directory: PathBuf,
+    plugin_directory: PathBuf,
+    data_directory: PathBuf,
+    profile_directory: PathBuf,
+    pending: FileChanges,
+    last_event: Option<Instant>,
+}
+
+impl FileWatcher {
+    /// Start watching the config in `directory` along with its plugin configs, and the waypoints in `data_directory`.
+    ///
+    /// Only the profiles directory is watched recursively, as the config directory might be the game's install
+    /// directory. The plugin and profiles directories are watched as soon as they're created, if they don't exist yet.
+    pub fn new(directory: &Path, data_directory: &Path) -> eyre::Result<Self> {
+        let directory = directory.canonicalize()?;
+        let plugin_directory = directory.join(PLUGIN_DIRECTORY_NAME);
+        let data_directory = data_directory.canonicalize()?;
+        let profile_directory = data_directory.join(PROFILE_DIRECTORY_NAME);
+
+        let (sender, events) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // The receiver only disappears when the watcher is dropped.
+            let _ = sender.send(event);
+        })?;
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+        if plugin_directory.is_dir() {
+            watcher.watch(&plugin_directory, RecursiveMode::NonRecursive)?;
+        }
+        if data_directory != directory {
+            watcher.watch(&data_directory, RecursiveMode::NonRecursive)?;
+        }
+        if profile_directory.is_dir() {
+            watcher.watch(&profile_directory, RecursiveMode::Recursive)?;
+        }
+
+        Ok(Self {
+            watcher,
+            events,
+            directory,
+            plugin_directory,
+            data_directory,
+            profile_directory,
+            pending: FileChanges::default(),
+            last_event: None,
+        })
+    }
+
+    /// Collect the events since the last call, returns the changed files once they've settled, see [DEBOUNCE].
+    pub fn poll(&mut self) -> FileChanges {
+        while let Ok(event) = self.events.try_recv() {
+            let event = match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_) | EventKind::Other) => event,
+                Ok(_) => continue,
+                Err(e) => {
+                    log::warn!("Error while watching the config files: {e}");
+                    continue;
+                }
+            };
+
+            for path in &event.paths {
+                if !matches!(event.kind, EventKind::Remove(_)) {
+                    self.watch_created(path);
+                }
+                match self.classify(path) {
+                    Some(WatchedFile::Config) => self.pending.config = true,
+                    Some(WatchedFile::Plugin) => self.pending.plugins = true,
+                    Some(WatchedFile::Waypoints) => self.pending.waypoints = true,
+                    None => continue,
+                }
+                self.last_event = Some(Instant::now());
+            }
+        }
+
+        match self.last_event {
+            Some(last) if last.elapsed() >= DEBOUNCE => {
+                self.last_event = None;
+                std::mem::take(&mut self.pending)
+            }
+            _ => FileChanges::default(),
+        }
+    }
+
+    /// Start watching the plugin or profiles directory at `path` once it has been created (or moved into place).
+    fn watch_created(&mut self, path: &Path) {
+        let mode = if path == self.plugin_directory {
+            RecursiveMode::NonRecursive
+        } else if path == self.profile_directory {
+            RecursiveMode::Recursive
+        } else {
+            return;
+        };
+        if !path.is_dir() {
+            return;
+        }
+
+        if let Err(e) = self.watcher.watch(path, mode) {
+            log::warn!("Failed to watch {path:?}, changes to it are only picked up after reloading: {e}");
+        }
+    }
+
+    fn classify(&self, path: &Path) -> Option<WatchedFile> {
+        // A directory which was (re)created might already contain files.
+        if path == self.plugin_directory {
+            return Some(WatchedFile::Plugin);
+        } else if path == self.profile_directory {
+            return Some(WatchedFile::Waypoints);
+        }
+
+        let parent = path.parent()?;
+        let name = path.file_name()?;
+
+        if name == WAYPOINTS_FILE_NAME && (parent == self.data_directory || parent.starts_with(&self.profile_directory))
+        {
+            Some(WatchedFile::Waypoints)
+        } else if parent == self.plugin_directory {
+            Format::from_path(path).map(|_| WatchedFile::Plugin)
+        } else if parent != self.directory {
+            None
+        } else if name == SKIP_PLUGIN_FILENAME {
+            Some(WatchedFile::Plugin)
+        } else if path.file_stem()? == CONFIG_FILE_STEM && Format::from_path(path).is_some() {
+            Some(WatchedFile::Config)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poll `watcher` until `expected` changes are reported, or give up after a few seconds.
+    fn wait_for(watcher: &mut FileWatcher, expected: FileChanges) {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            let changes = watcher.poll();
+            if changes == expected {
+                return;
+            }
+            assert_eq!(changes, FileChanges::default(), "Unexpected changes");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        panic!("{expected:?} weren't reported");
+    }
+
+    #[test]
+    fn config_changes() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut watcher = FileWatcher::new(directory.path(), directory.path()).unwrap();
+
+        std::fs::write(directory.path().join("skip_rs_config.toml"), "").unwrap();
+        std::fs::write(directory.path().join("unrelated.toml"), "").unwrap();
+        let config = FileChanges {
+            config: true,
+            ..Default::default()
+        };
+        wait_for(&mut watcher, config);
+    }
+
+    #[test]
+    fn directories_created_later() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut watcher = FileWatcher::new(directory.path(), directory.path()).unwrap();
+        let plugins = FileChanges {
+            plugins: true,
+            ..Default::default()
+        };
+        let waypoints = FileChanges {
+            waypoints: true,
+            ..Default::default()
+        };
+
+        let plugin_directory = directory.path().join(PLUGIN_DIRECTORY_NAME);
+        std::fs::create_dir(&plugin_directory).unwrap();
+        wait_for(&mut watcher, plugins);
+        std::fs::write(plugin_directory.join("game.json"), "{}").unwrap();
+        wait_for(&mut watcher, plugins);
+
+        let profile_directory = directory.path().join(PROFILE_DIRECTORY_NAME).join("any_percent");
+        std::fs::create_dir_all(&profile_directory).unwrap();
+        wait_for(&mut watcher, waypoints);
+        std::fs::write(profile_directory.join(WAYPOINTS_FILE_NAME), "{}").unwrap();
+        wait_for(&mut watcher, waypoints);
+    }
+}
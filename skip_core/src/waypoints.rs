@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use crate::migrations;
+use crate::migrations::{Migration, Versioned};
+
+pub type Waypoint = crate::plugins::PlayerCoordinates;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct WaypointSave {
+    /// The format version of this file, files from older versions are upgraded automatically.
+    #[serde(default = "crate::migrations::current_version::<WaypointSave>")]
+    pub version: u32,
+    pub most_recent: Option<Waypoint>,
+    /// Waypoints saved to a numbered slot, see the `slot` parameter of the `save_waypoint` action.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub slots: BTreeMap<u32, Waypoint>,
+}
+
+impl Default for WaypointSave {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            most_recent: None,
+            slots: BTreeMap::new(),
+        }
+    }
+}
+
+impl Versioned for WaypointSave {
+    const NAME: &'static str = "waypoints";
+    const MIGRATIONS: &'static [Migration] = &[migrations::add_version];
+}
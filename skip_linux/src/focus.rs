@@ -0,0 +1,109 @@
+//! Whether the game's window has focus, through the window manager's `_NET_ACTIVE_WINDOW` on X11 (and XWayland).
+//!
+//! Keys are read from `evdev` regardless of the focused window (see [crate::input]), so without this check keybinds
+//! would fire while typing in another window.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{Atom, AtomEnum, ConnectionExt, Window};
+use x11rb::rust_connection::RustConnection;
+
+/// How long a result is reused, as the focus is checked every frame.
+const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct FocusTracker {
+    pid: u32,
+    x11: Option<X11>,
+    /// The last result along with when it was determined.
+    last: Cell<Option<(Instant, bool)>>,
+}
+
+impl FocusTracker {
+    /// Track whether a window of process `pid` has focus.
+    ///
+    /// Without an X server (e.g. for native Wayland games) the focus can't be determined, which is logged.
+    pub fn new(pid: libc::pid_t) -> Self {
+        let x11 = X11::connect()
+            .inspect_err(|e| {
+                log::warn!(
+                    "Couldn't connect to the X server, so it's unknown whether the game is focused. Keybinds only work \
+                    with `global_keybinds` enabled: {e}"
+                )
+            })
+            .ok();
+
+        Self {
+            pid: pid as u32,
+            x11,
+            last: Cell::new(None),
+        }
+    }
+
+    /// Whether a window of the process has focus, [None] if that can't be determined.
+    pub fn is_focused(&self) -> Option<bool> {
+        let x11 = self.x11.as_ref()?;
+        if let Some((checked, focused)) = self.last.get() {
+            if checked.elapsed() < CHECK_INTERVAL {
+                return Some(focused);
+            }
+        }
+
+        let focused = match x11.focused_pid() {
+            Ok(pid) => pid == Some(self.pid),
+            Err(e) => {
+                log::debug!("Failed to get the focused window: {e}");
+                return None;
+            }
+        };
+        self.last.set(Some((Instant::now(), focused)));
+
+        Some(focused)
+    }
+}
+
+struct X11 {
+    connection: RustConnection,
+    root: Window,
+    active_window: Atom,
+    wm_pid: Atom,
+}
+
+impl X11 {
+    fn connect() -> eyre::Result<Self> {
+        let (connection, screen) = x11rb::connect(None)?;
+        let root = connection.setup().roots[screen].root;
+        let active_window = connection.intern_atom(false, b"_NET_ACTIVE_WINDOW")?.reply()?.atom;
+        let wm_pid = connection.intern_atom(false, b"_NET_WM_PID")?.reply()?.atom;
+
+        Ok(Self {
+            connection,
+            root,
+            active_window,
+            wm_pid,
+        })
+    }
+
+    /// The process owning the focused window, if the window manager and the window tell.
+    fn focused_pid(&self) -> eyre::Result<Option<u32>> {
+        let Some(window) = self.property(self.root, self.active_window, AtomEnum::WINDOW)? else {
+            return Ok(None);
+        };
+        if window == x11rb::NONE {
+            return Ok(None);
+        }
+
+        self.property(window, self.wm_pid, AtomEnum::CARDINAL)
+    }
+
+    /// The first 32 bit value of `property` on `window`.
+    fn property(&self, window: Window, property: Atom, kind: AtomEnum) -> eyre::Result<Option<u32>> {
+        let reply = self
+            .connection
+            .get_property(false, window, property, kind, 0, 1)?
+            .reply()?;
+
+        Ok(reply.value32().and_then(|mut values| values.next()))
+    }
+}
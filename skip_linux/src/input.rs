@@ -0,0 +1,259 @@
+//! Keyboard input through the kernel's `evdev` interface (`/dev/input/event*`).
+//!
+//! This works regardless of the display server (or Wine), but does require read access to the devices, which usually
+//! means the user has to be in the `input` group. Key presses are seen regardless of the focused window, hosts check
+//! the focus with [crate::focus].
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::os::unix::fs::OpenOptionsExt;
+
+use skip_core::keys::{KeyInput, VirtualKey};
+
+/// `struct input_event`, a `timeval` followed by the type, code, and value.
+const EVENT_SIZE: usize = std::mem::size_of::<libc::timeval>() + 8;
+const EV_KEY: u16 = 0x01;
+
+pub struct EvdevInput {
+    devices: Vec<File>,
+    held: HashSet<VirtualKey>,
+    /// Keys which went down since the last frame.
+    pressed: HashSet<VirtualKey>,
+}
+
+impl EvdevInput {
+    /// Open all readable input devices.
+    ///
+    /// # Errors
+    ///
+    /// If not a single device could be opened.
+    pub fn open_all() -> eyre::Result<Self> {
+        let mut devices = Vec::new();
+
+        for entry in std::fs::read_dir("/dev/input")? {
+            let path = entry?.path();
+            let is_event_device = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("event"));
+            if !is_event_device {
+                continue;
+            }
+
+            match std::fs::OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(&path)
+            {
+                Ok(file) => devices.push(file),
+                Err(e) => log::debug!("Skipping input device {path:?}: {e}"),
+            }
+        }
+
+        eyre::ensure!(
+            !devices.is_empty(),
+            "No input device in `/dev/input` could be opened, is the current user in the `input` group?"
+        );
+        log::info!("Listening for keybinds on {} input devices", devices.len());
+
+        Ok(Self {
+            devices,
+            held: HashSet::new(),
+            pressed: HashSet::new(),
+        })
+    }
+
+    /// Process all events which arrived since the last poll.
+    fn poll(&mut self) {
+        let mut buf = [0u8; EVENT_SIZE * 64];
+
+        for device in &mut self.devices {
+            loop {
+                let read = match device.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) => {
+                        log::trace!("Failed to read input device: {e}");
+                        break;
+                    }
+                };
+
+                for event in buf[..read].chunks_exact(EVENT_SIZE) {
+                    let kind = u16::from_ne_bytes([event[EVENT_SIZE - 8], event[EVENT_SIZE - 7]]);
+                    let code = u16::from_ne_bytes([event[EVENT_SIZE - 6], event[EVENT_SIZE - 5]]);
+                    let value = i32::from_ne_bytes(event[EVENT_SIZE - 4..].try_into().unwrap());
+
+                    if kind != EV_KEY {
+                        continue;
+                    }
+
+                    for key in virtual_keys(code) {
+                        match value {
+                            // Press
+                            1 => {
+                                self.held.insert(*key);
+                                self.pressed.insert(*key);
+                            }
+                            // Release, auto-repeats (2) are ignored.
+                            0 => {
+                                self.held.remove(key);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl KeyInput for EvdevInput {
+    fn all_pressed(&mut self, keys: &[VirtualKey]) -> bool {
+        self.poll();
+
+        !keys.is_empty()
+            && keys.iter().all(|key| self.held.contains(key))
+            && keys.iter().any(|key| self.pressed.contains(key))
+    }
+
+    fn end_frame(&mut self) {
+        self.pressed.clear();
+    }
+}
+
+/// Translate an `evdev` key code to the [VirtualKey]s it represents.
+///
+/// Modifiers map to both their sided and generic key, as configs generally use the latter (`VK_CONTROL`).
+fn virtual_keys(code: u16) -> &'static [VirtualKey] {
+    use VirtualKey::*;
+
+    match code {
+        1 => &[VK_ESCAPE],
+        2 => &[VK_1],
+        3 => &[VK_2],
+        4 => &[VK_3],
+        5 => &[VK_4],
+        6 => &[VK_5],
+        7 => &[VK_6],
+        8 => &[VK_7],
+        9 => &[VK_8],
+        10 => &[VK_9],
+        11 => &[VK_0],
+        12 => &[VK_OEM_MINUS],
+        13 => &[VK_OEM_PLUS],
+        14 => &[VK_BACK],
+        15 => &[VK_TAB],
+        16 => &[VK_Q],
+        17 => &[VK_W],
+        18 => &[VK_E],
+        19 => &[VK_R],
+        20 => &[VK_T],
+        21 => &[VK_Y],
+        22 => &[VK_U],
+        23 => &[VK_I],
+        24 => &[VK_O],
+        25 => &[VK_P],
+        26 => &[VK_OEM_4],
+        27 => &[VK_OEM_6],
+        28 => &[VK_RETURN],
+        29 => &[VK_LCONTROL, VK_CONTROL],
+        30 => &[VK_A],
+        31 => &[VK_S],
+        32 => &[VK_D],
+        33 => &[VK_F],
+        34 => &[VK_G],
+        35 => &[VK_H],
+        36 => &[VK_J],
+        37 => &[VK_K],
+        38 => &[VK_L],
+        39 => &[VK_OEM_1],
+        40 => &[VK_OEM_7],
+        41 => &[VK_OEM_3],
+        42 => &[VK_LSHIFT, VK_SHIFT],
+        43 => &[VK_OEM_5],
+        44 => &[VK_Z],
+        45 => &[VK_X],
+        46 => &[VK_C],
+        47 => &[VK_V],
+        48 => &[VK_B],
+        49 => &[VK_N],
+        50 => &[VK_M],
+        51 => &[VK_OEM_COMMA],
+        52 => &[VK_OEM_PERIOD],
+        53 => &[VK_OEM_2],
+        54 => &[VK_RSHIFT, VK_SHIFT],
+        55 => &[VK_MULTIPLY],
+        56 => &[VK_LMENU, VK_MENU],
+        57 => &[VK_SPACE],
+        58 => &[VK_CAPITAL],
+        59 => &[VK_F1],
+        60 => &[VK_F2],
+        61 => &[VK_F3],
+        62 => &[VK_F4],
+        63 => &[VK_F5],
+        64 => &[VK_F6],
+        65 => &[VK_F7],
+        66 => &[VK_F8],
+        67 => &[VK_F9],
+        68 => &[VK_F10],
+        69 => &[VK_NUMLOCK],
+        70 => &[VK_SCROLL],
+        71 => &[VK_NUMPAD7],
+        72 => &[VK_NUMPAD8],
+        73 => &[VK_NUMPAD9],
+        74 => &[VK_SUBTRACT],
+        75 => &[VK_NUMPAD4],
+        76 => &[VK_NUMPAD5],
+        77 => &[VK_NUMPAD6],
+        78 => &[VK_ADD],
+        79 => &[VK_NUMPAD1],
+        80 => &[VK_NUMPAD2],
+        81 => &[VK_NUMPAD3],
+        82 => &[VK_NUMPAD0],
+        83 => &[VK_DECIMAL],
+        86 => &[VK_OEM_102],
+        87 => &[VK_F11],
+        88 => &[VK_F12],
+        96 => &[VK_RETURN],
+        97 => &[VK_RCONTROL, VK_CONTROL],
+        98 => &[VK_DIVIDE],
+        99 => &[VK_SNAPSHOT],
+        100 => &[VK_RMENU, VK_MENU],
+        102 => &[VK_HOME],
+        103 => &[VK_UP],
+        104 => &[VK_PRIOR],
+        105 => &[VK_LEFT],
+        106 => &[VK_RIGHT],
+        107 => &[VK_END],
+        108 => &[VK_DOWN],
+        109 => &[VK_NEXT],
+        110 => &[VK_INSERT],
+        111 => &[VK_DELETE],
+        113 => &[VK_VOLUME_MUTE],
+        114 => &[VK_VOLUME_DOWN],
+        115 => &[VK_VOLUME_UP],
+        119 => &[VK_PAUSE],
+        125 => &[VK_LWIN],
+        126 => &[VK_RWIN],
+        127 => &[VK_APPS],
+        183 => &[VK_F13],
+        184 => &[VK_F14],
+        185 => &[VK_F15],
+        186 => &[VK_F16],
+        187 => &[VK_F17],
+        188 => &[VK_F18],
+        189 => &[VK_F19],
+        190 => &[VK_F20],
+        191 => &[VK_F21],
+        192 => &[VK_F22],
+        193 => &[VK_F23],
+        194 => &[VK_F24],
+        272 => &[VK_LBUTTON],
+        273 => &[VK_RBUTTON],
+        274 => &[VK_MBUTTON],
+        275 => &[VK_XBUTTON1],
+        276 => &[VK_XBUTTON2],
+        _ => &[],
+    }
+}
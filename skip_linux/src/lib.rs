@@ -0,0 +1,15 @@
+//! Linux host of SkipTheRunback.
+//!
+//! Built as a shared library which is loaded into native games through `LD_PRELOAD`, see [preload]. The process
+//! inspection parts work on any process id, and can therefore also be used from outside the game.
+#![cfg(target_os = "linux")]
+
+pub mod focus;
+pub mod input;
+pub mod maps;
+pub mod memory;
+pub mod platform;
+#[cfg(feature = "preload")]
+pub mod preload;
+pub mod process;
+pub mod scanner;
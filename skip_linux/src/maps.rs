@@ -0,0 +1,262 @@
+//! Parsing of `/proc/<pid>/maps`, which describes every mapping in a process' address space.
+
+use skip_core::scanner::Module;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapEntry {
+    pub start: usize,
+    pub end: usize,
+    pub readable: bool,
+    pub writable: bool,
+    pub executable: bool,
+    /// Whether changes to this mapping are visible to other processes (`s`), as opposed to copy-on-write (`p`).
+    pub shared: bool,
+    /// The file backing this mapping, or a pseudo path like `[heap]`. [None] for anonymous mappings.
+    pub path: Option<String>,
+}
+
+impl MapEntry {
+    pub fn size(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether this mapping is backed by an actual file, e.g. an ELF segment.
+    pub fn is_file(&self) -> bool {
+        self.path.as_deref().is_some_and(|path| path.starts_with('/'))
+    }
+
+    /// The file name of the backing file, which is used as the module name.
+    pub fn file_name(&self) -> Option<&str> {
+        self.path
+            .as_deref()
+            .filter(|_| self.is_file())
+            .and_then(|path| path.trim_end_matches(" (deleted)").rsplit('/').next())
+    }
+}
+
+/// Read the current mappings of process `pid`.
+pub fn read_maps(pid: libc::pid_t) -> eyre::Result<Vec<MapEntry>> {
+    let data = std::fs::read_to_string(format!("/proc/{pid}/maps"))?;
+    Ok(parse_maps(&data))
+}
+
+/// Parse the contents of a `maps` file, lines which can't be parsed are skipped.
+pub fn parse_maps(data: &str) -> Vec<MapEntry> {
+    data.lines().filter_map(parse_line).collect()
+}
+
+/// Parse a line in the `start-end perms offset dev inode [path]` format, e.g.
+/// `55d0c2a4b000-55d0c2a4d000 r-xp 00002000 08:01 1234    /usr/bin/game`.
+fn parse_line(line: &str) -> Option<MapEntry> {
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let perms = fields.next()?.as_bytes();
+    // Offset, device, and inode
+    fields.nth(2)?;
+    let path = fields.next().map(str::trim).filter(|path| !path.is_empty());
+
+    if perms.len() < 4 {
+        return None;
+    }
+
+    Some(MapEntry {
+        start: usize::from_str_radix(start, 16).ok()?,
+        end: usize::from_str_radix(end, 16).ok()?,
+        readable: perms[0] == b'r',
+        writable: perms[1] == b'w',
+        executable: perms[2] == b'x',
+        shared: perms[3] == b's',
+        path: path.map(str::to_string),
+    })
+}
+
+/// Group the file backed mappings into modules, in order of their base address.
+///
+/// A file can be mapped more than once (e.g. when a debugger reads the executable), only consecutive mappings are
+/// considered to be part of the same module.
+///
+/// The anonymous mapping directly following a module's last segment is included as well, as that's where its `.bss`
+/// (and thus most static pointers) ends up.
+pub fn modules(entries: &[MapEntry]) -> Vec<Module> {
+    let mut output: Vec<Module> = Vec::new();
+    let mut last_module: Option<usize> = None;
+
+    for entry in entries {
+        match entry.file_name() {
+            // Consecutive mappings of the same file are the segments of a single module.
+            Some(name) => match last_module.map(|index| &mut output[index]) {
+                Some(module) if module.name == name => {
+                    module.size = entry.end - module.base;
+                }
+                _ => {
+                    output.push(Module {
+                        name: name.to_string(),
+                        base: entry.start,
+                        size: entry.size(),
+                    });
+                    last_module = Some(output.len() - 1);
+                }
+            },
+            None if entry.path.is_none() => {
+                if let Some(module) = last_module.map(|index| &mut output[index]) {
+                    if module.base + module.size == entry.start {
+                        module.size += entry.size();
+                    }
+                }
+                last_module = None;
+            }
+            None => last_module = None,
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPS: &str = "\
+55d0c2a49000-55d0c2a4b000 r--p 00000000 08:01 1234                       /usr/bin/game
+55d0c2a4b000-55d0c2a4d000 r-xp 00002000 08:01 1234                       /usr/bin/game
+55d0c2a4d000-55d0c2a4e000 rw-p 00004000 08:01 1234                       /usr/bin/game
+55d0c2a4e000-55d0c2a50000 rw-p 00000000 00:00 0 
+55d0c3a00000-55d0c3a21000 rw-p 00000000 00:00 0                          [heap]
+7f1e2c000000-7f1e2c021000 rw-p 00000000 00:00 0
+7f1e2d000000-7f1e2d001000 r-xp 00000000 08:01 5678                       /home/user/My Games/libgame.so (deleted)
+7f1e2d001000-7f1e2d002000 rw-s 00000000 00:05 9                          /dev/shm/shared
+7ffd4a1e0000-7ffd4a201000 rw-p 00000000 00:00 0                          [stack]
+";
+
+    fn entry(line: &str) -> MapEntry {
+        parse_line(line).unwrap()
+    }
+
+    #[test]
+    fn file_mapping() {
+        let entry = entry("55d0c2a4b000-55d0c2a4d000 r-xp 00002000 08:01 1234    /usr/bin/game");
+
+        assert_eq!(
+            entry,
+            MapEntry {
+                start: 0x55d0c2a4b000,
+                end: 0x55d0c2a4d000,
+                readable: true,
+                writable: false,
+                executable: true,
+                shared: false,
+                path: Some("/usr/bin/game".into()),
+            }
+        );
+        assert_eq!(entry.size(), 0x2000);
+        assert_eq!(entry.file_name(), Some("game"));
+    }
+
+    #[test]
+    fn anonymous_mapping() {
+        for line in [
+            "7f1e2c000000-7f1e2c021000 rw-p 00000000 00:00 0",
+            "7f1e2c000000-7f1e2c021000 rw-p 00000000 00:00 0 ",
+        ] {
+            let entry = entry(line);
+            assert_eq!(entry.path, None);
+            assert!(!entry.is_file());
+            assert_eq!(entry.file_name(), None);
+        }
+    }
+
+    #[test]
+    fn pseudo_paths() {
+        for (line, path) in [
+            (
+                "55d0c3a00000-55d0c3a21000 rw-p 00000000 00:00 0          [heap]",
+                "[heap]",
+            ),
+            (
+                "7ffd4a1e0000-7ffd4a201000 rw-p 00000000 00:00 0          [stack]",
+                "[stack]",
+            ),
+        ] {
+            let entry = entry(line);
+            assert_eq!(entry.path.as_deref(), Some(path));
+            assert!(!entry.is_file());
+            assert_eq!(entry.file_name(), None);
+        }
+    }
+
+    #[test]
+    fn path_with_spaces() {
+        let entry = entry("7f1e2d000000-7f1e2d001000 r-xp 00000000 08:01 5678    /home/user/My Games/game");
+
+        assert_eq!(entry.path.as_deref(), Some("/home/user/My Games/game"));
+        assert_eq!(entry.file_name(), Some("game"));
+    }
+
+    #[test]
+    fn deleted_file() {
+        let entry = entry("7f1e2d000000-7f1e2d001000 r-xp 00000000 08:01 5678    /tmp/libgame.so (deleted)");
+
+        assert!(entry.is_file());
+        assert_eq!(entry.file_name(), Some("libgame.so"));
+    }
+
+    #[test]
+    fn shared_mapping() {
+        let entry = entry("7f1e2d001000-7f1e2d002000 rw-s 00000000 00:05 9    /dev/shm/shared");
+
+        assert!(entry.shared);
+        assert!(entry.writable);
+        assert!(!entry.executable);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert_eq!(parse_line(""), None);
+        assert_eq!(parse_line("not a mapping"), None);
+        assert_eq!(parse_line("55d0c2a4b000-55d0c2a4d000 r- 00002000 08:01 1234"), None);
+        assert_eq!(parse_line("55d0c2a4b000-zzzz r-xp 00002000 08:01 1234"), None);
+        assert_eq!(parse_maps(&format!("garbage\n{MAPS}")).len(), 9);
+    }
+
+    #[test]
+    fn modules_from_maps() {
+        let modules = modules(&parse_maps(MAPS));
+
+        assert_eq!(
+            modules,
+            [
+                // The segments of the executable, including the `.bss` directly after it, but not the heap.
+                Module {
+                    name: "game".into(),
+                    base: 0x55d0c2a49000,
+                    size: 0x7000,
+                },
+                Module {
+                    name: "libgame.so".into(),
+                    base: 0x7f1e2d000000,
+                    size: 0x1000,
+                },
+                Module {
+                    name: "shared".into(),
+                    base: 0x7f1e2d001000,
+                    size: 0x1000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn remapped_file_is_separate_module() {
+        let maps = "\
+1000-2000 r-xp 00000000 08:01 1234    /usr/bin/game
+2000-3000 rw-p 00000000 00:00 0          [heap]
+4000-5000 r--p 00000000 08:01 1234    /usr/bin/game
+5000-6000 rw-p 00000000 00:00 0
+";
+        let modules = modules(&parse_maps(maps));
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!((modules[0].base, modules[0].size), (0x1000, 0x1000));
+        assert_eq!((modules[1].base, modules[1].size), (0x4000, 0x2000));
+    }
+}
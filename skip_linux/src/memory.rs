@@ -0,0 +1,98 @@
+use skip_core::memory::{checked_range, MemoryAccess, MemoryError, MemoryResult};
+
+/// The (exclusive) end of the user-space part of the address space on x86-64.
+const USER_SPACE_END: usize = 0x0000_8000_0000_0000;
+
+/// [MemoryAccess] for any process (including our own) through `process_vm_readv`/`process_vm_writev`.
+///
+/// These respect the page protections of the target, and never transfer part of a single access, so that memory which
+/// is freed or protected in the meantime results in an error rather than a segfault (or a torn write).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessVmMemory {
+    pid: libc::pid_t,
+}
+
+impl ProcessVmMemory {
+    pub fn new(pid: libc::pid_t) -> Self {
+        Self { pid }
+    }
+
+    /// Memory of the current process.
+    pub fn current() -> Self {
+        Self::new(std::process::id() as libc::pid_t)
+    }
+
+    pub fn pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    fn validate(&self, address: usize, len: usize) -> MemoryResult<()> {
+        let end = checked_range(address, len)?;
+
+        if end > USER_SPACE_END {
+            return Err(MemoryError::OutOfProcess { address });
+        }
+
+        Ok(())
+    }
+}
+
+impl MemoryAccess for ProcessVmMemory {
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> MemoryResult<()> {
+        self.validate(address, buf.len())?;
+
+        let local = libc::iovec {
+            iov_base: buf.as_mut_ptr().cast(),
+            iov_len: buf.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        let transferred = unsafe { libc::process_vm_readv(self.pid, &local, 1, &remote, 1, 0) };
+
+        check_transfer(transferred, address, buf.len(), MemoryError::NotReadable { address })
+    }
+
+    fn write_bytes(&self, address: usize, data: &[u8]) -> MemoryResult<()> {
+        self.validate(address, data.len())?;
+
+        let local = libc::iovec {
+            iov_base: data.as_ptr() as *mut libc::c_void,
+            iov_len: data.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as *mut libc::c_void,
+            iov_len: data.len(),
+        };
+        let transferred = unsafe { libc::process_vm_writev(self.pid, &local, 1, &remote, 1, 0) };
+
+        check_transfer(transferred, address, data.len(), MemoryError::NotWritable { address })
+    }
+}
+
+/// Turn the result of a `process_vm_*` call into a [MemoryResult], where `on_fault` is returned for inaccessible memory.
+fn check_transfer(transferred: isize, address: usize, len: usize, on_fault: MemoryError) -> MemoryResult<()> {
+    if transferred < 0 {
+        let error = std::io::Error::last_os_error();
+
+        return Err(match error.raw_os_error() {
+            Some(libc::EFAULT) => on_fault,
+            _ => MemoryError::AccessFailed {
+                address,
+                len,
+                reason: error.to_string(),
+            },
+        });
+    }
+
+    if transferred as usize != len {
+        return Err(MemoryError::AccessFailed {
+            address,
+            len,
+            reason: format!("only {transferred:#X} bytes were transferred"),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use eyre::ContextCompat;
+use skip_core::memory::MemoryAccess;
+use skip_core::platform::{HitListener, Hook, Platform};
+use skip_core::scanner::{MemorySource, Module, ScanOptions};
+use skip_core::signature::Signature;
+
+use crate::memory::ProcessVmMemory;
+use crate::scanner::MapsMemorySource;
+use crate::{maps, process};
+
+/// [Platform] for a Linux process, which is our own process when loaded through `LD_PRELOAD`.
+#[derive(Debug, Clone)]
+pub struct LinuxPlatform {
+    memory: Arc<ProcessVmMemory>,
+}
+
+impl LinuxPlatform {
+    pub fn new(pid: libc::pid_t) -> Self {
+        Self {
+            memory: Arc::new(ProcessVmMemory::new(pid)),
+        }
+    }
+
+    /// The platform for the current process.
+    pub fn current() -> Self {
+        Self {
+            memory: Arc::new(ProcessVmMemory::current()),
+        }
+    }
+
+    pub fn pid(&self) -> libc::pid_t {
+        self.memory.pid()
+    }
+
+    fn is_current(&self) -> bool {
+        self.pid() == std::process::id() as libc::pid_t
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        maps::read_maps(self.pid())
+            .map(|entries| maps::modules(&entries))
+            .unwrap_or_default()
+    }
+}
+
+impl Platform for LinuxPlatform {
+    fn memory(&self) -> Arc<dyn MemoryAccess> {
+        self.memory.clone()
+    }
+
+    fn main_module(&self) -> Option<Module> {
+        self.module(&process::process_name(self.pid())?)
+    }
+
+    fn module(&self, name: &str) -> Option<Module> {
+        self.modules().into_iter().find(|module| module.name == name)
+    }
+
+    /// Only the executable segments of the module are searched, as the gaps between an ELF's segments aren't mapped.
+    fn find_signature(&self, module: Option<&str>, signature: &str) -> eyre::Result<usize> {
+        let module = match module {
+            Some(name) => self
+                .module(name)
+                .with_context(|| format!("Module `{name}` is not loaded"))?,
+            None => self.main_module().context("Failed to get the main module")?,
+        };
+        let signature = Signature::parse(signature)?;
+        let segments = maps::read_maps(self.pid())?
+            .into_iter()
+            .filter(|entry| entry.readable && entry.executable && entry.file_name() == Some(&module.name));
+
+        for segment in segments {
+            let mut data = vec![0; segment.size()];
+            if let Err(e) = self.memory.read_bytes(segment.start, &mut data) {
+                log::debug!("Failed to read segment {:#X} of `{}`: {e}", segment.start, module.name);
+                continue;
+            }
+
+            if let Some(offset) = signature.find(&data) {
+                return Ok(segment.start + offset);
+            }
+        }
+
+        eyre::bail!("Signature not found in `{}`", module.name)
+    }
+
+    fn attach(&self, address: usize, listener: Box<dyn HitListener>) -> eyre::Result<Hook> {
+        eyre::ensure!(
+            self.is_current(),
+            "Hooks can only be attached from within the game process"
+        );
+
+        #[cfg(feature = "preload")]
+        return skip_core::frida::attach(address, listener);
+
+        #[cfg(not(feature = "preload"))]
+        {
+            let _ = (address, listener);
+            eyre::bail!("Hooks are only available in the `LD_PRELOAD` host")
+        }
+    }
+
+    fn memory_source(&self, options: ScanOptions) -> Box<dyn MemorySource> {
+        Box::new(MapsMemorySource {
+            memory: *self.memory,
+            options,
+        })
+    }
+}
@@ -0,0 +1,175 @@
+//! The `LD_PRELOAD` entry point, e.g. `LD_PRELOAD=/path/to/libskip_linux.so %command%` as Steam launch option.
+//!
+//! The config and save files live next to the shared library, just like the DLL on Windows. Only processes which a plugin
+//! applies to are attached to, by their executable's name (`expected_exe_name`) or an already loaded `expected_module`.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use eyre::ContextCompat;
+use log::LevelFilter;
+use skip_core::config;
+use skip_core::config::SkipConfig;
+use skip_core::paths;
+use skip_core::plugins;
+use skip_core::runner::{Host, Runner};
+
+use crate::focus::FocusTracker;
+use crate::input::EvdevInput;
+use crate::maps;
+use crate::platform::LinuxPlatform;
+
+static SHUTDOWN_FLAG: AtomicBool = AtomicBool::new(false);
+
+#[used]
+#[link_section = ".init_array"]
+static INIT: extern "C" fn() = preload_init;
+
+#[used]
+#[link_section = ".fini_array"]
+static FINI: extern "C" fn() = preload_fini;
+
+extern "C" fn preload_init() {
+    let Ok(library_path) = library_path() else {
+        return;
+    };
+    // Processes started by this one (e.g. by Steam's launch wrappers, or the game's crash reporter) shouldn't load us.
+    remove_from_ld_preload(&library_path);
+
+    // Run on our own thread, so we never block the game's startup.
+    std::thread::spawn(move || {
+        if let Err(e) = preload_attach(&library_path) {
+            log::error!("SkipTheRunback stopped: {e:?}");
+        }
+    });
+}
+
+extern "C" fn preload_fini() {
+    SHUTDOWN_FLAG.store(true, Ordering::SeqCst);
+}
+
+pub fn preload_attach(library_path: &Path) -> eyre::Result<()> {
+    let install_directory = library_path.parent().context("Library is in root")?;
+    let save_config_directory = &paths::config_directory(install_directory)?;
+    let platform = Arc::new(LinuxPlatform::current());
+
+    // Everything started with our `LD_PRELOAD` loads us, including launch wrappers which mustn't be touched. Nothing is
+    // logged or written before we know this is a game.
+    let is_game = plugins::plugin_identifiers(save_config_directory)
+        .iter()
+        .any(|identifiers| identifiers.matches(&*platform).is_match());
+    if !is_game {
+        return Ok(());
+    }
+
+    let cfg = simplelog::ConfigBuilder::new().build();
+    // Ignore result in case we have double initialisation of the library.
+    let _ = simplelog::SimpleLogger::init(LevelFilter::Trace, cfg);
+
+    log::info!("Using the config in {save_config_directory:?}");
+    config::create_initial_config(save_config_directory)?;
+    // A broken config must never take the game down with it, the defaults work until it has been fixed.
+    let conf = config::load_config(save_config_directory).unwrap_or_else(|e| {
+        log::error!("Failed to validate the config, the default config is used until it has been fixed: {e:#}");
+        SkipConfig::default()
+    });
+
+    log::info!("Loaded config: {:#?}", conf);
+
+    let mut key_manager = EvdevInput::open_all()?;
+    let mut host = LinuxHost {
+        config_dir: save_config_directory.to_path_buf(),
+        focus: FocusTracker::new(platform.pid()),
+        global_keybinds: conf.global_keybinds,
+    };
+
+    let mut runner = Runner::new(save_config_directory, &conf, platform)?;
+
+    runner.run_until(conf, &mut key_manager, &mut host, &SHUTDOWN_FLAG)
+}
+
+/// Remove this library from `LD_PRELOAD`, keeping any others (like the Steam overlay).
+fn remove_from_ld_preload(library_path: &Path) {
+    let Some(preload) = std::env::var_os("LD_PRELOAD") else {
+        return;
+    };
+    let own_name = library_path.file_name();
+
+    // Entries can be separated by colons or spaces.
+    let preload = preload.to_string_lossy();
+    let others = preload
+        .split([':', ' '])
+        .filter(|entry| !entry.is_empty() && Path::new(entry).file_name() != own_name)
+        .collect::<Vec<_>>();
+
+    if others.is_empty() {
+        std::env::remove_var("LD_PRELOAD");
+    } else {
+        std::env::set_var("LD_PRELOAD", others.join(":"));
+    }
+}
+
+/// Find the path of this shared library through the mapping which contains our own code.
+fn library_path() -> eyre::Result<PathBuf> {
+    let own_address = preload_init as *const () as usize;
+    let entries = maps::read_maps(std::process::id() as libc::pid_t)?;
+    let entry = entries
+        .into_iter()
+        .find(|entry| (entry.start..entry.end).contains(&own_address) && entry.is_file())
+        .context("Failed to find the mapping of the SkipTheRunback library")?;
+
+    Ok(PathBuf::from(entry.path.context("Library mapping has no path")?))
+}
+
+struct LinuxHost {
+    config_dir: PathBuf,
+    focus: FocusTracker,
+    /// See [SkipConfig::global_keybinds].
+    global_keybinds: bool,
+}
+
+impl Host for LinuxHost {
+    fn is_foreground(&self) -> bool {
+        self.focus.is_focused().unwrap_or(self.global_keybinds)
+    }
+
+    fn reload_config(&mut self, _old: &SkipConfig) -> eyre::Result<SkipConfig> {
+        let conf = reload_config(&self.config_dir)?;
+        self.global_keybinds = conf.global_keybinds;
+        Ok(conf)
+    }
+}
+
+fn reload_config(config_dir: &Path) -> eyre::Result<SkipConfig> {
+    log::debug!("Reloading config");
+    let conf = config::reload_config(config_dir)?;
+
+    log::debug!("New config loaded: {:#?}", conf);
+
+    Ok(conf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ld_preload_keeps_other_libraries() {
+        let own = Path::new("/opt/skip/libskip_linux.so");
+
+        std::env::set_var(
+            "LD_PRELOAD",
+            "/steam/gameoverlayrenderer.so /opt/skip/libskip_linux.so:/other/lib.so",
+        );
+        remove_from_ld_preload(own);
+        assert_eq!(
+            std::env::var("LD_PRELOAD").unwrap(),
+            "/steam/gameoverlayrenderer.so:/other/lib.so"
+        );
+
+        std::env::set_var("LD_PRELOAD", "libskip_linux.so");
+        remove_from_ld_preload(own);
+        assert!(std::env::var_os("LD_PRELOAD").is_none());
+    }
+}
@@ -0,0 +1,34 @@
+//! Finding other processes through `/proc`.
+
+/// Find the id of a running process by the file name of its executable, e.g. `game.x86_64`.
+///
+/// Processes of other users can usually only be matched on their (truncated) `comm` name.
+pub fn find_process(name: &str) -> Option<libc::pid_t> {
+    let own_pid = std::process::id() as libc::pid_t;
+
+    std::fs::read_dir("/proc")
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<libc::pid_t>().ok())
+        .filter(|pid| *pid != own_pid)
+        .find(|pid| {
+            // `comm` is truncated to 15 characters
+            process_name(*pid)
+                .is_some_and(|process| process == name || (process.len() == 15 && name.starts_with(&process)))
+        })
+}
+
+/// The file name of the executable of process `pid`.
+pub fn process_name(pid: libc::pid_t) -> Option<String> {
+    if let Ok(exe) = std::fs::read_link(format!("/proc/{pid}/exe")) {
+        return exe.file_name().map(|name| name.to_string_lossy().into_owned());
+    }
+
+    std::fs::read_to_string(format!("/proc/{pid}/comm"))
+        .ok()
+        .map(|comm| comm.trim_end().to_string())
+}
+
+/// Whether process `pid` is still running.
+pub fn is_alive(pid: libc::pid_t) -> bool {
+    std::path::Path::new(&format!("/proc/{pid}")).exists()
+}
@@ -0,0 +1,52 @@
+use skip_core::memory::MemoryAccess;
+use skip_core::scanner::{MemoryRegion, MemorySource, Module, ScanOptions};
+
+use crate::maps;
+use crate::memory::ProcessVmMemory;
+
+/// [MemorySource] for any process, based on its `/proc/<pid>/maps`.
+///
+/// Anonymous mappings and the heap are the equivalent of private memory on Windows, while the file backed writable
+/// mappings are the data segments of the loaded modules.
+#[derive(Debug, Clone)]
+pub struct MapsMemorySource {
+    pub memory: ProcessVmMemory,
+    pub options: ScanOptions,
+}
+
+impl MemorySource for MapsMemorySource {
+    fn regions(&self) -> Vec<MemoryRegion> {
+        let Ok(entries) = maps::read_maps(self.memory.pid()) else {
+            return Vec::new();
+        };
+
+        entries
+            .into_iter()
+            .filter(|entry| entry.readable && entry.writable && !entry.shared)
+            .filter(|entry| match entry.path.as_deref() {
+                None | Some("[heap]") => true,
+                Some(_) => self.options.include_modules && entry.is_file(),
+            })
+            .filter(|entry| {
+                self.options
+                    .max_region_size
+                    .map(|max| entry.size() <= max)
+                    .unwrap_or(true)
+            })
+            .map(|entry| MemoryRegion {
+                base: entry.start,
+                size: entry.size(),
+            })
+            .collect()
+    }
+
+    fn read(&self, address: usize, buf: &mut [u8]) -> eyre::Result<()> {
+        Ok(self.memory.read_bytes(address, buf)?)
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        maps::read_maps(self.memory.pid())
+            .map(|entries| maps::modules(&entries))
+            .unwrap_or_default()
+    }
+}
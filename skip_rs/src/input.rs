@@ -0,0 +1,16 @@
+use rust_hooking_utils::raw_input::key_manager::KeyboardManager;
+use skip_core::keys::{KeyInput, VirtualKey};
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+
+/// [KeyInput] backed by the raw Windows keyboard state.
+pub struct KeyboardInput(pub KeyboardManager);
+
+impl KeyInput for KeyboardInput {
+    fn all_pressed(&mut self, keys: &[VirtualKey]) -> bool {
+        self.0.all_pressed(keys.iter().map(|key| VIRTUAL_KEY(key.code())))
+    }
+
+    fn end_frame(&mut self) {
+        self.0.end_frame()
+    }
+}
@@ -1,41 +1,43 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::sync::Arc;
 
 use eyre::{ContextCompat, Result};
 use log::LevelFilter;
 use rust_hooking_utils::patching::process::GameProcess;
 use rust_hooking_utils::raw_input::key_manager::KeyboardManager;
-use rust_hooking_utils::raw_input::virtual_keys::VirtualKey;
+use skip_core::config;
+use skip_core::config::SkipConfig;
+use skip_core::paths;
+use skip_core::platform::Platform;
+use skip_core::runner::{Host, Runner};
 use windows::core::HSTRING;
 use windows::Win32::Foundation::HWND;
-use windows::Win32::UI::WindowsAndMessaging::{MB_OK, MessageBoxExW};
+use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, MB_OK, MessageBoxExW};
 
-use crate::config::SkipConfig;
+mod input;
 
-mod app;
-mod config;
-mod waypoints;
-
-mod utils;
-
-pub mod plugins;
+pub mod memory;
+pub mod platform;
+pub mod scanner;
 
 static SHUTDOWN_FLAG: AtomicBool = AtomicBool::new(false);
+/// Whether a config error was shown in a message box since the config last loaded, see [load_validated_config].
+static ERROR_SHOWN: AtomicBool = AtomicBool::new(false);
 
 pub fn dll_attach(hinst_dll: windows::Win32::Foundation::HMODULE) -> Result<()> {
     let dll_path = rust_hooking_utils::get_current_dll_path(hinst_dll).map_err(|e| eyre::eyre!(Box::new(e)))?;
-    let save_config_directory = dll_path.parent().context("DLL is in root")?;
+    let install_directory = dll_path.parent().context("DLL is in root")?;
     let cfg = simplelog::ConfigBuilder::new().build();
 
     // Ignore result in case we have double initialisation of the DLL.
     simplelog::SimpleLogger::init(LevelFilter::Trace, cfg)?;
 
+    let save_config_directory = &paths::config_directory(install_directory)?;
     config::create_initial_config(save_config_directory)?;
 
-    let Ok(mut conf) = load_validated_config(save_config_directory, None) else {
-        std::process::exit(1)
-    };
+    // A broken config must never take the game down with it, the defaults work until it has been fixed.
+    let conf = load_validated_config(save_config_directory, None).unwrap_or_default();
 
     if conf.console {
         unsafe {
@@ -51,40 +53,16 @@ pub fn dll_attach(hinst_dll: windows::Win32::Foundation::HMODULE) -> Result<()>
 
     log::info!("Found main window: {:?} ({:?})", main_window.title(), main_window.0);
 
-    let mut key_manager = KeyboardManager::new();
-    let update_duration = Duration::from_secs_f64(1.0 / 60.);
-
-    let plugins = plugins::get_all_plugins(save_config_directory);
-
-    let plugin_to_use = plugins
-        .into_iter()
-        .find(|p| p.should_apply())
-        .context("No applicable plugin could be found, disabling SkipTheRunback")?;
-
-    log::info!(
-        "Found `{}` as the plugin to use for skipping runback",
-        plugin_to_use.identifiers().plugin_name
-    );
-
-    let mut app = app::SkipApp::new(save_config_directory, plugin_to_use)?;
-
-    while !SHUTDOWN_FLAG.load(Ordering::Acquire) {
-        if let Some(reload) = &conf.reload_config_keys {
-            if key_manager.all_pressed(reload.iter().copied().map(VirtualKey::to_virtual_key)) {
-                conf = reload_config(save_config_directory, &mut conf, main_window.0)?;
-            }
-        }
-
-        // Only run if we're in the foreground. A bit hacky, but eh...
-        if main_window.is_foreground_window() {
-            app.run(&conf, &mut key_manager)?;
-        }
+    let mut key_manager = input::KeyboardInput(KeyboardManager::new());
+    let mut host = WindowsHost {
+        config_dir: save_config_directory.to_path_buf(),
+        main_window: main_window.0,
+    };
 
-        std::thread::sleep(update_duration);
-        key_manager.end_frame();
-    }
+    let platform: Arc<dyn Platform> = Arc::new(platform::WindowsPlatform);
+    let mut runner = Runner::new(save_config_directory, &conf, platform)?;
 
-    Ok(())
+    runner.run_until(conf, &mut key_manager, &mut host, &SHUTDOWN_FLAG)
 }
 
 pub fn dll_detach(_hinst_dll: windows::Win32::Foundation::HMODULE) -> Result<()> {
@@ -94,7 +72,22 @@ pub fn dll_detach(_hinst_dll: windows::Win32::Foundation::HMODULE) -> Result<()>
     Ok(())
 }
 
-fn reload_config(config_dir: impl AsRef<Path>, old: &mut SkipConfig, parent_window: HWND) -> eyre::Result<SkipConfig> {
+struct WindowsHost {
+    config_dir: PathBuf,
+    main_window: HWND,
+}
+
+impl Host for WindowsHost {
+    fn is_foreground(&self) -> bool {
+        unsafe { GetForegroundWindow() == self.main_window }
+    }
+
+    fn reload_config(&mut self, old: &SkipConfig) -> eyre::Result<SkipConfig> {
+        reload_config(&self.config_dir, old, self.main_window)
+    }
+}
+
+fn reload_config(config_dir: impl AsRef<Path>, old: &SkipConfig, parent_window: HWND) -> eyre::Result<SkipConfig> {
     log::debug!("Reloading config");
     let conf = load_validated_config(config_dir.as_ref(), Some(parent_window))?;
 
@@ -115,10 +108,30 @@ fn reload_config(config_dir: impl AsRef<Path>, old: &mut SkipConfig, parent_wind
 }
 
 fn load_validated_config(config_dir: &Path, parent_window: Option<HWND>) -> eyre::Result<SkipConfig> {
-    match config::load_config(config_dir) {
-        Ok(conf) => Ok(conf),
+    let result = if parent_window.is_some() {
+        config::reload_config(config_dir)
+    } else {
+        config::load_config(config_dir)
+    };
+
+    match result {
+        Ok(conf) => {
+            // Once the config is fixed the next mistake is worth a message box again.
+            ERROR_SHOWN.store(false, Ordering::SeqCst);
+            Ok(conf)
+        }
         Err(e) => unsafe {
-            let message = format!("Error: {}\nSkipRun will now exit", e);
+            let consequence = if parent_window.is_some() {
+                "The previous config stays in use"
+            } else {
+                "The default config is used until it has been fixed"
+            };
+            log::error!("Failed to validate the config: {e:#}. {consequence}");
+            // With hot reloading every save of a half-edited config fails, a message box each time would be unbearable.
+            if ERROR_SHOWN.swap(true, Ordering::SeqCst) {
+                return Err(e);
+            }
+            let message = format!("Error: {}\n{consequence}\n\nFurther errors are only logged", e);
             let _ = MessageBoxExW(
                 parent_window.unwrap_or_default(),
                 &HSTRING::from(message),
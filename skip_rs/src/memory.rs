@@ -0,0 +1,124 @@
+use std::ffi::c_void;
+
+use once_cell::sync::Lazy;
+use windows::Win32::System::Diagnostics::Debug::{ReadProcessMemory, WriteProcessMemory};
+use windows::Win32::System::Memory::{
+    VirtualQuery, MEMORY_BASIC_INFORMATION, MEM_COMMIT, PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE,
+    PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_NOACCESS, PAGE_PROTECTION_FLAGS, PAGE_READONLY, PAGE_READWRITE,
+    PAGE_WRITECOPY,
+};
+use windows::Win32::System::SystemInformation::{GetSystemInfo, SYSTEM_INFO};
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+use skip_core::memory::{checked_range, MemoryAccess, MemoryError, MemoryResult};
+
+const READABLE: u32 = PAGE_READONLY.0
+    | PAGE_READWRITE.0
+    | PAGE_WRITECOPY.0
+    | PAGE_EXECUTE_READ.0
+    | PAGE_EXECUTE_READWRITE.0
+    | PAGE_EXECUTE_WRITECOPY.0;
+const WRITABLE: u32 = PAGE_READWRITE.0 | PAGE_WRITECOPY.0 | PAGE_EXECUTE_READWRITE.0 | PAGE_EXECUTE_WRITECOPY.0;
+
+/// The lowest and highest (inclusive) address which user-mode code in this process can access.
+static APPLICATION_RANGE: Lazy<(usize, usize)> = Lazy::new(|| {
+    let mut info = SYSTEM_INFO::default();
+    unsafe { GetSystemInfo(&mut info) };
+
+    (
+        info.lpMinimumApplicationAddress as usize,
+        info.lpMaximumApplicationAddress as usize,
+    )
+});
+
+/// [MemoryAccess] for the current process.
+///
+/// Every access first checks the page protections of the entire range, and then goes through
+/// `ReadProcessMemory`/`WriteProcessMemory`, so that memory which is freed in between still results in an error rather
+/// than an access violation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessMemory;
+
+impl ProcessMemory {
+    fn validate(&self, address: usize, len: usize, required: u32) -> MemoryResult<()> {
+        let end = checked_range(address, len)?;
+        let (min, max) = *APPLICATION_RANGE;
+
+        if address < min || end.saturating_sub(1) > max {
+            return Err(MemoryError::OutOfProcess { address });
+        }
+
+        let mut current = address;
+        let mut info = MEMORY_BASIC_INFORMATION::default();
+
+        while current < end {
+            let written = unsafe {
+                VirtualQuery(
+                    Some(current as *const c_void),
+                    &mut info,
+                    std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+                )
+            };
+
+            if written == 0 || info.State != MEM_COMMIT {
+                return Err(MemoryError::Unmapped { address: current });
+            }
+
+            if !has_protection(info.Protect, required) {
+                return Err(if required == WRITABLE {
+                    MemoryError::NotWritable { address: current }
+                } else {
+                    MemoryError::NotReadable { address: current }
+                });
+            }
+
+            current = info.BaseAddress as usize + info.RegionSize;
+        }
+
+        Ok(())
+    }
+}
+
+impl MemoryAccess for ProcessMemory {
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> MemoryResult<()> {
+        self.validate(address, buf.len(), READABLE)?;
+
+        unsafe {
+            ReadProcessMemory(
+                GetCurrentProcess(),
+                address as *const c_void,
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                None,
+            )
+        }
+        .map_err(|e| MemoryError::AccessFailed {
+            address,
+            len: buf.len(),
+            reason: e.to_string(),
+        })
+    }
+
+    fn write_bytes(&self, address: usize, data: &[u8]) -> MemoryResult<()> {
+        self.validate(address, data.len(), WRITABLE)?;
+
+        unsafe {
+            WriteProcessMemory(
+                GetCurrentProcess(),
+                address as *const c_void,
+                data.as_ptr() as *const c_void,
+                data.len(),
+                None,
+            )
+        }
+        .map_err(|e| MemoryError::AccessFailed {
+            address,
+            len: data.len(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+fn has_protection(protection: PAGE_PROTECTION_FLAGS, required: u32) -> bool {
+    protection.0 & required != 0 && protection.0 & (PAGE_GUARD.0 | PAGE_NOACCESS.0) == 0
+}
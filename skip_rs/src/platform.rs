@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use rust_hooking_utils::patching::process::GameProcess;
+use skip_core::memory::MemoryAccess;
+use skip_core::platform::{HitListener, Hook, Platform};
+use skip_core::scanner::{MemorySource, Module, ScanOptions};
+
+use crate::memory::ProcessMemory;
+use crate::scanner::{process_modules, ProcessMemorySource};
+
+/// [Platform] for the (Windows) process we've been injected in.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowsPlatform;
+
+impl Platform for WindowsPlatform {
+    fn memory(&self) -> Arc<dyn MemoryAccess> {
+        Arc::new(ProcessMemory)
+    }
+
+    fn main_module(&self) -> Option<Module> {
+        process_modules().into_iter().next()
+    }
+
+    fn module(&self, name: &str) -> Option<Module> {
+        process_modules()
+            .into_iter()
+            .find(|module| module.name.eq_ignore_ascii_case(name))
+    }
+
+    fn find_signature(&self, module: Option<&str>, signature: &str) -> eyre::Result<usize> {
+        let process = GameProcess::current_process();
+        let module = if let Some(module) = module { process.get_module(module)? } else { process.get_base_module()? };
+
+        Ok(module
+            .to_local()?
+            .scan_for_pattern(signature)
+            .map_err(|e| eyre::eyre!(Box::new(e)))? as usize)
+    }
+
+    fn attach(&self, address: usize, listener: Box<dyn HitListener>) -> eyre::Result<Hook> {
+        skip_core::frida::attach(address, listener)
+    }
+
+    fn memory_source(&self, options: ScanOptions) -> Box<dyn MemorySource> {
+        Box::new(ProcessMemorySource { options })
+    }
+}
@@ -0,0 +1,105 @@
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::System::Memory::{
+    VirtualQuery, MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_PRIVATE, PAGE_GUARD, PAGE_NOACCESS,
+    PAGE_READWRITE, PAGE_WRITECOPY,
+};
+use windows::Win32::System::ProcessStatus::{EnumProcessModules, GetModuleBaseNameW, GetModuleInformation, MODULEINFO};
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+use skip_core::memory::MemoryAccess;
+use skip_core::scanner::{MemoryRegion, MemorySource, Module, ScanOptions};
+
+use crate::memory::ProcessMemory;
+
+/// [MemorySource] for the current process.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMemorySource {
+    pub options: ScanOptions,
+}
+
+impl MemorySource for ProcessMemorySource {
+    fn regions(&self) -> Vec<MemoryRegion> {
+        let mut output = Vec::new();
+        let mut address = 0usize;
+        let mut info = MEMORY_BASIC_INFORMATION::default();
+
+        loop {
+            let written = unsafe {
+                VirtualQuery(
+                    Some(address as *const c_void),
+                    &mut info,
+                    std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+                )
+            };
+
+            if written == 0 {
+                break;
+            }
+
+            let region = MemoryRegion {
+                base: info.BaseAddress as usize,
+                size: info.RegionSize,
+            };
+            let is_writable = info.Protect.0 & (PAGE_READWRITE.0 | PAGE_WRITECOPY.0) != 0
+                && info.Protect.0 & (PAGE_GUARD.0 | PAGE_NOACCESS.0) == 0;
+            let is_small_enough = self
+                .options
+                .max_region_size
+                .map(|max| region.size <= max)
+                .unwrap_or(true);
+            let is_wanted_type = info.Type == MEM_PRIVATE || (self.options.include_modules && info.Type == MEM_IMAGE);
+
+            if info.State == MEM_COMMIT && is_wanted_type && is_writable && is_small_enough {
+                output.push(region);
+            }
+
+            let Some(next) = region.base.checked_add(region.size) else {
+                break;
+            };
+            address = next;
+        }
+
+        output
+    }
+
+    fn read(&self, address: usize, buf: &mut [u8]) -> eyre::Result<()> {
+        Ok(ProcessMemory.read_bytes(address, buf)?)
+    }
+
+    fn modules(&self) -> Vec<Module> {
+        process_modules()
+    }
+}
+
+/// All modules loaded in the current process, the first of which is the executable itself.
+pub fn process_modules() -> Vec<Module> {
+    let mut handles = vec![HMODULE::default(); 1024];
+    let mut needed = 0u32;
+
+    unsafe {
+        let process = GetCurrentProcess();
+        let size = std::mem::size_of_val(handles.as_slice()) as u32;
+        if EnumProcessModules(process, handles.as_mut_ptr(), size, &mut needed).is_err() {
+            return Vec::new();
+        }
+        handles.truncate(needed as usize / std::mem::size_of::<HMODULE>());
+
+        handles
+            .into_iter()
+            .filter_map(|handle| {
+                let mut info = MODULEINFO::default();
+                GetModuleInformation(process, handle, &mut info, std::mem::size_of::<MODULEINFO>() as u32).ok()?;
+                let mut name = [0u16; 260];
+                let len = GetModuleBaseNameW(process, handle, &mut name) as usize;
+
+                Some(Module {
+                    name: String::from_utf16_lossy(&name[..len]),
+                    base: info.lpBaseOfDll as usize,
+                    size: info.SizeOfImage as usize,
+                })
+            })
+            .collect()
+    }
+}
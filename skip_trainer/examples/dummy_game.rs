@@ -0,0 +1,83 @@
+//! A stand-in game for trying out the trainer.
+//!
+//! The player position lives in a heap allocated struct which is only reachable through a static pointer, a matching
+//! plugin config is written to the given directory. Run it with `cargo run -p skip_trainer --example dummy_game -- <dir>`
+//! and attach with `skip_trainer dummy_game <dir>`. The player moves one unit along `x` every second, so teleports show
+//! up as jumps in the logged position.
+
+#[cfg(target_os = "linux")]
+fn main() -> eyre::Result<()> {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicPtr, Ordering};
+    use std::time::Duration;
+
+    use eyre::ContextCompat;
+    use skip_core::migrations::Versioned;
+    use skip_core::plugins::generic::*;
+    use skip_core::schema;
+
+    #[repr(C)]
+    struct Player {
+        _padding: [u8; 0x80],
+        position: [f32; 3],
+    }
+
+    static PLAYER: AtomicPtr<Player> = AtomicPtr::new(std::ptr::null_mut());
+
+    let directory = match std::env::args().nth(1) {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir()?,
+    };
+
+    let player = Box::leak(Box::new(Player {
+        _padding: [0; 0x80],
+        position: [0.0, 10.0, 0.0],
+    }));
+    PLAYER.store(player, Ordering::SeqCst);
+
+    let pid = std::process::id() as libc::pid_t;
+    let exe = skip_linux::process::process_name(pid).context("Failed to get our own executable")?;
+    let entries = skip_linux::maps::read_maps(pid)?;
+    let module = skip_linux::maps::modules(&entries)
+        .into_iter()
+        .find(|module| module.name == exe)
+        .context("Failed to find our own module")?;
+    let static_offset = &PLAYER as *const _ as usize - module.base;
+
+    let config = GenericConfig {
+        version: GenericConfig::VERSION,
+        extends: None,
+        identifiers: PluginIdentifiers {
+            plugin_name: "Dummy Game".into(),
+            expected_module: None,
+            expected_exe_name: Some(exe.clone()),
+        },
+        position: GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Chain(PointerChain {
+            base: RelativePointer::new(&exe, static_offset),
+            offsets: vec![0],
+        })),
+        pointer_offsets: OffsetsConfig {
+            x: 0x80,
+            y: 0x84,
+            z: 0x88,
+        },
+    };
+    let plugin_file = directory.join(SKIP_PLUGIN_FILENAME);
+    std::fs::write(&plugin_file, schema::to_json_with_schema(&config)?)?;
+    println!("Running as process {pid}, wrote plugin config to {plugin_file:?}");
+
+    loop {
+        let position = unsafe { std::ptr::addr_of_mut!((*PLAYER.load(Ordering::SeqCst)).position) };
+        let mut current = unsafe { position.read_volatile() };
+        current[0] += 1.0;
+        unsafe { position.write_volatile(current) };
+
+        println!("Player position: {current:?}");
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    eprintln!("The dummy game is only supported on Linux");
+}
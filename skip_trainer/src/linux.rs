@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use eyre::ContextCompat;
+use skip_core::config;
+use skip_core::config::SkipConfig;
+use skip_core::platform::Platform;
+use skip_core::plugins::generic::{ConfigBasedPlugin, GenericPositionConfig};
+use skip_core::plugins::SkipPlugin;
+use skip_core::runner::{Host, Runner};
+use skip_linux::focus::FocusTracker;
+use skip_linux::input::EvdevInput;
+use skip_linux::platform::LinuxPlatform;
+use skip_linux::process;
+
+static SHUTDOWN_FLAG: AtomicBool = AtomicBool::new(false);
+
+pub fn run(target: &str, save_directory: &Path) -> eyre::Result<()> {
+    let pid = match target.parse::<libc::pid_t>() {
+        Ok(pid) => pid,
+        Err(_) => process::find_process(target).with_context(|| format!("No running process called `{target}`"))?,
+    };
+    log::info!("Attaching to process {pid}");
+
+    config::create_initial_config(save_directory)?;
+    // Like the hosts inside the game, a broken config falls back to the defaults until it has been fixed.
+    let conf = config::load_config(save_directory).unwrap_or_else(|e| {
+        log::error!("Failed to validate the config, the default config is used until it has been fixed: {e:#}");
+        SkipConfig::default()
+    });
+
+    log::info!("Loaded config: {:#?}", conf);
+
+    let platform: Arc<dyn Platform> = Arc::new(LinuxPlatform::new(pid));
+    let plugins = external_plugins(save_directory, &platform)?;
+    let mut runner = Runner::with_plugins(save_directory, &conf, platform, plugins)?;
+
+    let mut key_manager = EvdevInput::open_all()?;
+    let mut host = TrainerHost {
+        config_dir: save_directory.to_path_buf(),
+        focus: FocusTracker::new(pid),
+        global_keybinds: conf.global_keybinds,
+    };
+
+    // Stop once the game does.
+    std::thread::spawn(move || {
+        while process::is_alive(pid) {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        log::info!("Process {pid} exited, stopping");
+        SHUTDOWN_FLAG.store(true, Ordering::SeqCst);
+    });
+
+    runner.run_until(conf, &mut key_manager, &mut host, &SHUTDOWN_FLAG)
+}
+
+/// All plugins which can be driven from outside the game, i.e. those which don't require an intercept.
+fn external_plugins(save_directory: &Path, platform: &Arc<dyn Platform>) -> eyre::Result<Vec<Box<dyn SkipPlugin>>> {
+    let plugins = ConfigBasedPlugin::load_all(save_directory, platform)?;
+
+    Ok(plugins
+        .into_iter()
+        .filter(|plugin| {
+            let supported = matches!(plugin.config().position, GenericPositionConfig::AbsolutePtr(_));
+            if !supported {
+                log::warn!(
+                    "Skipping plugin `{}`, intercepts aren't supported by the external trainer",
+                    plugin.config().identifiers.plugin_name
+                );
+            }
+            supported
+        })
+        .map(|plugin| Box::new(plugin) as Box<dyn SkipPlugin>)
+        .collect())
+}
+
+struct TrainerHost {
+    config_dir: PathBuf,
+    /// Tracks the focus of the game's window, not the trainer's.
+    focus: FocusTracker,
+    /// See [SkipConfig::global_keybinds].
+    global_keybinds: bool,
+}
+
+impl Host for TrainerHost {
+    fn is_foreground(&self) -> bool {
+        self.focus.is_focused().unwrap_or(self.global_keybinds)
+    }
+
+    fn reload_config(&mut self, _old: &SkipConfig) -> eyre::Result<SkipConfig> {
+        log::debug!("Reloading config");
+        let conf = config::reload_config(&self.config_dir)?;
+        self.global_keybinds = conf.global_keybinds;
+
+        log::debug!("New config loaded: {:#?}", conf);
+
+        Ok(conf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skip_core::plugins::generic::PLUGIN_DIRECTORY_NAME;
+
+    fn write_plugin(directory: &Path, name: &str, position: &str) {
+        let config = format!(
+            r#"version = 1
+            [identifiers]
+            plugin_name = "{name}"
+            expected_exe_name = "game"
+            {position}
+            [pointer_offsets]
+            x = 0
+            y = 4
+            z = 8"#
+        );
+        std::fs::write(directory.join(format!("{name}.toml")), config).unwrap();
+    }
+
+    #[test]
+    fn intercept_plugins_are_skipped() {
+        let directory = tempfile::tempdir().unwrap();
+        let plugins = directory.path().join(PLUGIN_DIRECTORY_NAME);
+        std::fs::create_dir(&plugins).unwrap();
+        write_plugin(
+            &plugins,
+            "Intercept",
+            "[position.InterceptPtr]\nintercept_signature = \"0F 28\"\nregister = \"Rcx\"",
+        );
+        write_plugin(&plugins, "Absolute", "[position.AbsolutePtr]\nAbsolute = \"0x1000\"");
+
+        let platform: Arc<dyn Platform> = Arc::new(LinuxPlatform::current());
+        assert_eq!(
+            ConfigBasedPlugin::load_all(directory.path(), &platform).unwrap().len(),
+            2
+        );
+        let names = external_plugins(directory.path(), &platform)
+            .unwrap()
+            .iter()
+            .map(|plugin| plugin.identifiers().plugin_name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Absolute"]);
+    }
+}
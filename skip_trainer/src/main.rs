@@ -0,0 +1,43 @@
+//! Out-of-process trainer, for games which refuse to load the proxy DLL.
+//!
+//! Attaches to a running game and drives the same plugins through cross-process memory access. As no hooks can be
+//! placed from the outside, only plugins with a static pointer or pointer chain as position source are supported.
+//!
+//! Usage: `skip_trainer <process name or pid> [config directory]`, where the config directory defaults to the one picked
+//! by `skip_core::paths::config_directory` for the directory of the trainer itself.
+
+use std::path::PathBuf;
+
+use eyre::ContextCompat;
+use log::LevelFilter;
+use skip_core::paths;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+fn main() -> eyre::Result<()> {
+    let cfg = simplelog::ConfigBuilder::new().build();
+    simplelog::SimpleLogger::init(LevelFilter::Debug, cfg)?;
+
+    let mut args = std::env::args().skip(1);
+    let target = args
+        .next()
+        .context("Usage: skip_trainer <process name or pid> [config directory]")?;
+    let save_directory = match args.next() {
+        Some(dir) => PathBuf::from(dir),
+        None => paths::config_directory(std::env::current_exe()?.parent().context("Trainer is in root")?)?,
+    };
+    log::info!("Using the config in {save_directory:?}");
+
+    run(&target, save_directory)
+}
+
+#[cfg(target_os = "linux")]
+fn run(target: &str, save_directory: PathBuf) -> eyre::Result<()> {
+    linux::run(target, &save_directory)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn run(_target: &str, _save_directory: PathBuf) -> eyre::Result<()> {
+    eyre::bail!("The external trainer is currently only supported on Linux")
+}
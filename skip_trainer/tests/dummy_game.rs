@@ -0,0 +1,133 @@
+//! Drives the plugin logic against the `dummy_game` example running as a separate process, the same way the trainer
+//! attaches to a game.
+#![cfg(target_os = "linux")]
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+
+use skip_core::memory::MemoryAccess;
+use skip_core::plugins::generic::*;
+use skip_linux::memory::ProcessVmMemory;
+use skip_linux::platform::LinuxPlatform;
+
+/// The dummy game, killed once the test is done.
+struct DummyGame(Child);
+
+impl DummyGame {
+    /// Start the dummy game, and wait until it has written its plugin config to `directory`.
+    fn spawn(directory: &Path) -> Self {
+        // `cargo test` builds the examples next to the test binaries, in `target/<profile>/examples`.
+        let examples = std::env::current_exe()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .with_file_name("examples");
+        let exe = examples.join("dummy_game");
+        assert!(
+            exe.exists(),
+            "{exe:?} doesn't exist, build it with `cargo build -p skip_trainer --example dummy_game`"
+        );
+
+        let mut child = Command::new(exe).arg(directory).stdout(Stdio::piped()).spawn().unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        assert!(line.starts_with("Running as process"), "{line}");
+
+        Self(child)
+    }
+
+    fn pid(&self) -> libc::pid_t {
+        self.0.id() as libc::pid_t
+    }
+}
+
+impl Drop for DummyGame {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn plugin_file(directory: &Path) -> PathBuf {
+    directory.join(SKIP_PLUGIN_FILENAME)
+}
+
+#[test]
+fn pointer_chain() {
+    let directory = tempfile::tempdir().unwrap();
+    let game = DummyGame::spawn(directory.path());
+    let platform = Arc::new(LinuxPlatform::new(game.pid()));
+
+    let mut plugin = ConfigBasedPlugin::new(plugin_file(directory.path()), platform).unwrap();
+    assert!(matches!(
+        plugin.config().position,
+        GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Chain(_))
+    ));
+    plugin.start().unwrap();
+
+    let position = plugin.get_current_coordinates().unwrap().unwrap();
+    assert_eq!((position.y, position.z), (10.0, 0.0));
+
+    plugin
+        .set_current_coordinates(PlayerCoordinates {
+            x: 1000.0,
+            y: 20.0,
+            z: 30.0,
+        })
+        .unwrap();
+    let position = plugin.get_current_coordinates().unwrap().unwrap();
+    // The game keeps moving the player along `x`.
+    assert!((1000.0..1010.0).contains(&position.x), "{position:?}");
+    assert_eq!((position.y, position.z), (20.0, 30.0));
+}
+
+#[test]
+fn static_pointer() {
+    let directory = tempfile::tempdir().unwrap();
+    let game = DummyGame::spawn(directory.path());
+    let platform = Arc::new(LinuxPlatform::new(game.pid()));
+
+    // Resolve the chain ourselves, and use the player struct as a static pointer.
+    let mut config = ConfigBasedPlugin::new(plugin_file(directory.path()), platform.clone())
+        .unwrap()
+        .config()
+        .clone();
+    let GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Chain(chain)) = &config.position else {
+        panic!("The dummy game should use a pointer chain, not {:?}", config.position);
+    };
+    let player = chain.resolve(&*platform).unwrap();
+    config.position = GenericPositionConfig::AbsolutePtr(PointerTypeConfig::Absolute(player.try_into().unwrap()));
+
+    let mut plugin = ConfigBasedPlugin::from_config(config, platform);
+    plugin.start().unwrap();
+    plugin
+        .set_current_coordinates(PlayerCoordinates {
+            x: -500.0,
+            y: 5.0,
+            z: 6.0,
+        })
+        .unwrap();
+
+    let memory = ProcessVmMemory::new(game.pid());
+    assert_eq!(memory.read_f32(player + 0x84).unwrap(), 5.0);
+    assert_eq!(memory.read_f32(player + 0x88).unwrap(), 6.0);
+
+    memory.write_f32(player + 0x84, 7.0).unwrap();
+    let position = plugin.get_current_coordinates().unwrap().unwrap();
+    assert_eq!((position.y, position.z), (7.0, 6.0));
+}
+
+#[test]
+fn unmapped_memory() {
+    let directory = tempfile::tempdir().unwrap();
+    let game = DummyGame::spawn(directory.path());
+    let memory = ProcessVmMemory::new(game.pid());
+
+    assert!(memory.read_f32(0x10).is_err());
+    assert!(memory.write_f32(0x10, 1.0).is_err());
+    assert!(memory.read_f32(usize::MAX - 2).is_err());
+}